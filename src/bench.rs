@@ -0,0 +1,320 @@
+//! Benchmarking the solvers.
+//!
+//! Parsing the input and solving each part are timed separately, since
+//! they can differ a lot: day 15 parses in microseconds, but solving
+//! part two takes seconds. Each phase is run a number of times and
+//! summarized as the minimum, median, and maximum time.
+//!
+//! The timings can be saved as a baseline, which is a JSON file like
+//! this, and later runs compared against it:
+//!
+//! ```text
+//! {
+//!   "timings": [
+//!     {
+//!       "year": 2020,
+//!       "day": 1,
+//!       "phase": "parse",
+//!       "min_ns": 9250,
+//!       "median_ns": 9810,
+//!       "max_ns": 15003
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Baselines saved before there was more than one year have no
+//! `year` and are taken to be of the first year.
+
+use crate::{
+    error::{AocError, Result},
+    json::{self, Value},
+    registry::{Day, FIRST_YEAR},
+    runner::{format_duration, guard},
+    Part,
+};
+use std::{
+    convert::TryFrom,
+    fmt,
+    fs::{read_to_string, write},
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// Phase of solving a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Solve(part) => f.pad(&format!("part{}", part)),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<Phase> {
+        match text {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Solve(Part::One)),
+            "part2" => Ok(Phase::Solve(Part::Two)),
+            _ => Err(AocError::bad("unknown phase", text)),
+        }
+    }
+}
+
+/// Summary of the times of a number of runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarize the samples, which must not be empty.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort_unstable();
+        // With an odd number of samples, both indices are the middle.
+        let len = samples.len();
+        Stats {
+            min: samples[0],
+            median: (samples[(len - 1) / 2] + samples[len / 2]) / 2,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Timing of one phase of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub year: u32,
+    pub day: u32,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+impl Timing {
+    /// Relative change of the median time compared to the same phase
+    /// in the baseline, so `0.1` means 10% slower. `None` if the
+    /// phase is not in the baseline.
+    pub fn change(&self, baseline: &[Timing]) -> Option<f64> {
+        let base = baseline.iter().find(|timing| {
+            (timing.year, timing.day, timing.phase) == (self.year, self.day, self.phase)
+        })?;
+        let base = base.stats.median.as_secs_f64();
+        if base > 0.0 {
+            Some(self.stats.median.as_secs_f64() / base - 1.0)
+        } else {
+            None
+        }
+    }
+}
+
+/// Time parsing and solving the given parts of a day over a number of
+/// iterations. Fails on the first error from the solver.
+pub fn bench(day: &Day, text: &str, parts: &[Part], iterations: usize) -> Result<Vec<Timing>> {
+    let mut parse_samples = vec![];
+    let mut part_samples = vec![vec![]; parts.len()];
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        let input = guard(|| day.solver.parse_input(text))?;
+        parse_samples.push(start.elapsed());
+        for (samples, &part) in part_samples.iter_mut().zip(parts) {
+            let start = Instant::now();
+            guard(|| day.solver.solve_part(input.as_ref(), part))?;
+            samples.push(start.elapsed());
+        }
+    }
+
+    let phases = std::iter::once(Phase::Parse).chain(parts.iter().map(|&part| Phase::Solve(part)));
+    let samples = std::iter::once(parse_samples).chain(part_samples);
+    Ok(phases
+        .zip(samples)
+        .map(|(phase, samples)| Timing {
+            year: day.year,
+            day: day.number,
+            phase,
+            stats: Stats::from_samples(samples),
+        })
+        .collect())
+}
+
+/// Print the timings as a table with one row per phase. If there is a
+/// baseline, the change of the median time is shown as well, and
+/// phases that are more than `threshold` slower are marked as
+/// regressions. Returns the number of regressions.
+pub fn print_table(timings: &[Timing], baseline: &[Timing], threshold: f64) -> usize {
+    println!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>8}",
+        "Day", "Phase", "Min", "Median", "Max", "Change"
+    );
+    let mut regressions = 0;
+    for timing in timings {
+        let (change, mark) = match timing.change(baseline) {
+            Some(change) if change > threshold => {
+                regressions += 1;
+                (format!("{:+.1}%", change * 100.0), "  REGRESSION")
+            }
+            Some(change) => (format!("{:+.1}%", change * 100.0), ""),
+            None => (String::new(), ""),
+        };
+        println!(
+            "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>8}{}",
+            timing.day,
+            timing.phase,
+            format_duration(timing.stats.min),
+            format_duration(timing.stats.median),
+            format_duration(timing.stats.max),
+            change,
+            mark
+        );
+    }
+    regressions
+}
+
+fn nanos(duration: Duration) -> Value {
+    Value::Int(i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX))
+}
+
+/// Convert timings to the JSON baseline format.
+pub fn to_json(timings: &[Timing]) -> Value {
+    let timings = timings
+        .iter()
+        .map(|timing| {
+            Value::object(vec![
+                ("year", Value::from(timing.year)),
+                ("day", Value::from(timing.day)),
+                ("phase", Value::from(timing.phase.to_string())),
+                ("min_ns", nanos(timing.stats.min)),
+                ("median_ns", nanos(timing.stats.median)),
+                ("max_ns", nanos(timing.stats.max)),
+            ])
+        })
+        .collect();
+    Value::object(vec![("timings", Value::Array(timings))])
+}
+
+/// Read timings from the JSON baseline format.
+pub fn from_json(value: &Value) -> Result<Vec<Timing>> {
+    let timings = value
+        .get("timings")
+        .and_then(Value::as_array)
+        .ok_or("baseline has no timings")?;
+    timings
+        .iter()
+        .map(|timing| {
+            let int = |key| {
+                timing
+                    .get(key)
+                    .and_then(Value::as_i64)
+                    .ok_or_else(|| AocError::bad("missing or bad field", key))
+            };
+            let duration = |key| int(key).map(|n| Duration::from_nanos(n.max(0) as u64));
+            Ok(Timing {
+                year: match timing.get("year") {
+                    Some(_) => int("year")? as u32,
+                    None => FIRST_YEAR,
+                },
+                day: int("day")? as u32,
+                phase: timing
+                    .get("phase")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| AocError::bad("missing or bad field", "phase"))?
+                    .parse()?,
+                stats: Stats {
+                    min: duration("min_ns")?,
+                    median: duration("median_ns")?,
+                    max: duration("max_ns")?,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Save timings as a baseline file.
+pub fn save(path: &Path, timings: &[Timing]) -> Result<()> {
+    write(path, to_json(timings).pretty() + "\n")
+        .map_err(|err| AocError::new(format!("{}: {}", path.display(), err)))
+}
+
+/// Load timings from a baseline file.
+pub fn load(path: &Path) -> Result<Vec<Timing>> {
+    let file = path.display().to_string();
+    let text = read_to_string(path).map_err(|err| AocError::new(format!("{}: {}", file, err)))?;
+    json::parse(&text)
+        .and_then(|value| from_json(&value))
+        .map_err(|err| err.in_file(&file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(vec![ms(5), ms(1), ms(3)]);
+        assert_eq!((stats.min, stats.median, stats.max), (ms(1), ms(3), ms(5)));
+        let stats = Stats::from_samples(vec![ms(4), ms(1), ms(2), ms(8)]);
+        assert_eq!((stats.min, stats.median, stats.max), (ms(1), ms(3), ms(8)));
+    }
+
+    #[test]
+    fn test_bench() {
+        let day = registry::find(2020, 1).unwrap();
+        let timings = bench(day, "1721\n979\n366\n299\n675\n1456\n", &[Part::Two], 3).unwrap();
+        let phases: Vec<_> = timings.iter().map(|timing| timing.phase).collect();
+        assert_eq!(phases, [Phase::Parse, Phase::Solve(Part::Two)]);
+        assert!(bench(day, "seven\n", &[Part::One], 3).is_err());
+    }
+
+    #[test]
+    fn test_baseline() {
+        let timing = |phase, median| Timing {
+            year: 2020,
+            day: 15,
+            phase,
+            stats: Stats {
+                min: ms(1),
+                median,
+                max: ms(20),
+            },
+        };
+        let baseline = vec![
+            timing(Phase::Parse, ms(10)),
+            timing(Phase::Solve(Part::Two), ms(10)),
+        ];
+        assert_eq!(
+            from_json(&json::parse(&to_json(&baseline).pretty()).unwrap()),
+            Ok(baseline.clone())
+        );
+
+        let change = timing(Phase::Solve(Part::Two), ms(12))
+            .change(&baseline)
+            .unwrap();
+        assert!((change - 0.2).abs() < 1e-9);
+        assert_eq!(
+            timing(Phase::Solve(Part::One), ms(12)).change(&baseline),
+            None
+        );
+
+        let old = r#"{"timings": [{"day": 15, "phase": "parse", "min_ns": 1000000, "median_ns": 10000000, "max_ns": 20000000}]}"#;
+        assert_eq!(
+            from_json(&json::parse(old).unwrap()),
+            Ok(vec![timing(Phase::Parse, ms(10))])
+        );
+    }
+}
@@ -0,0 +1,781 @@
+//! Runner for the solutions of all days.
+//!
+//! ```text
+//! aoc run <day> [--part <part>] [--input <path> | --example [<n>]] [--format <format>]
+//! aoc run --all [--part <part>] [--example [<n>]] [--format <format>]
+//!         [--jobs <n>] [--timeout <seconds>] [--no-cache]
+//! aoc bench <day> | --all [<run options>] [--iterations <n>]
+//!           [--save <baseline>] [--baseline <baseline> [--threshold <percent>]]
+//! aoc fetch <day> [--base-url <url>]
+//! aoc submit <day> <part> [--base-url <url>]
+//! aoc new <day> [--title <title>]
+//! aoc watch <day> [--interval <seconds>]
+//! aoc gen <day> [--size <n>] [--seed <seed>]
+//! aoc report [--out <dir>] [--format <format>] [--source-url <url>]
+//!            [--jobs <n>] [--timeout <seconds>]
+//! aoc serve [--port <port>] [--bind <address>]
+//! aoc verify <day> --inputs <dir> [--part <part>] [--jobs <n>] [--timeout <seconds>]
+//!            [--allow-unchecked]
+//! ```
+//!
+//! With `-v` or `-vv`, which can be given anywhere, the days write
+//! debug or trace output to standard error (see the `trace` module).
+//!
+//! The days are those of the latest year in the registry, unless
+//! another year is given with `--year`, which can also be given
+//! anywhere. With `--all`, all days of the year are run.
+//!
+//! The input is searched for as described in the `input` module,
+//! unless given with `--input`, where `-` reads standard input. With
+//! `--example`, the first example (or example `n`) is used instead
+//! of the puzzle input, and days without that example are skipped
+//! when running all days.
+//!
+//! The answers are printed as a table, or with `--format json` as a
+//! JSON array with one object per part (see `runner::to_json`), and
+//! the exit code is non-zero if any of the days failed.
+//!
+//! With `--jobs`, that many days are solved at the same time, and with
+//! `--timeout`, a day that is not solved within the time limit is
+//! reported as timed out instead of holding up the run.
+//!
+//! Answers are kept in a cache and not solved again for the same input
+//! and build, unless `--no-cache` is given (see the `cache` module). A
+//! day is only taken from the cache if all its parts are there.
+//!
+//! The `bench` command takes the same options as `run`, but times
+//! parsing and each part over a number of iterations (10 by default)
+//! instead of printing the answers. The timings can be saved as a
+//! baseline and compared against later, which makes the exit code
+//! non-zero if the median time of any phase is more than the
+//! threshold (10% by default) slower than in the baseline.
+//!
+//! The `fetch` command downloads the input of a day from the site,
+//! unless it is already there, and the `submit` command solves a part
+//! and submits the answer, unless it is known to be wrong (see the
+//! `submit` module). See the `site` module for the session token and
+//! base URL.
+//!
+//! The `new` command adds the files for a new day of the year to the
+//! source tree and registers it, see the `scaffold` module.
+//!
+//! The `watch` command solves a day and its examples again whenever
+//! its input or examples change, checking every `--interval` seconds
+//! (1 by default), and prints the answers that changed (see the
+//! `watch` module). It runs until interrupted.
+//!
+//! The `gen` command prints generated input for a day, of the given
+//! size or about the size of the puzzle input, which is the same for
+//! the same seed (0 by default). See the `generate` module.
+//!
+//! The `report` command solves all days and writes a static site into
+//! `--out` (`site` by default), as `html` or `markdown`, with a page
+//! per day holding the puzzle text from its module documentation and
+//! the answers (see the `report` module). The sources are linked below
+//! `--source-url`, or as local files if not given.
+//!
+//! The `serve` command answers `POST /{year}/day/{n}/part/{p}`
+//! requests with the puzzle input as body, where the year can be left
+//! out, on `--port` (2020 by default) of `--bind` (`127.0.0.1` by
+//! default), see the `serve` module. It runs until interrupted.
+//!
+//! The `verify` command solves a day for every input in the directory
+//! given with `--inputs`, like the inputs of several people, and
+//! prints a matrix of the inputs and parts that shows which answers
+//! match the `.expected` sidecars of the inputs (see the `verify`
+//! module). The exit code is non-zero if any of them did not, which
+//! includes parts without an expected answer unless `--allow-unchecked`
+//! is given.
+
+use adventofcode::{
+    bench::{self, Timing},
+    cache::{self, Cache},
+    fetch::{self, Fetched},
+    generate,
+    input::Resolver,
+    input::{self, InputError, Source},
+    registry::{self, Day},
+    report::{self, Page},
+    runner::{self, Outcome},
+    scaffold, serve,
+    site::Client,
+    submit::{self, Verdict},
+    trace, verify, watch, AocError, Part,
+};
+use std::{
+    collections::HashMap,
+    env, fs,
+    iter::Peekable,
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process, slice,
+    time::Duration,
+};
+
+const USAGE: &str = "\
+usage: aoc run <day> [--part <part>] [--input <path> | --example [<n>]] [--format <format>]
+       aoc run --all [--part <part>] [--example [<n>]] [--format <format>]
+               [--jobs <n>] [--timeout <seconds>] [--no-cache]
+       aoc bench <day> | --all [<run options>] [--iterations <n>]
+                 [--save <baseline>] [--baseline <baseline> [--threshold <percent>]]
+       aoc fetch <day> [--base-url <url>]
+       aoc submit <day> <part> [--base-url <url>]
+       aoc new <day> [--title <title>]
+       aoc watch <day> [--interval <seconds>]
+       aoc gen <day> [--size <n>] [--seed <seed>]
+       aoc report [--out <dir>] [--format <format>] [--source-url <url>]
+                  [--jobs <n>] [--timeout <seconds>]
+       aoc serve [--port <port>] [--bind <address>]
+       aoc verify <day> --inputs <dir> [--part <part>] [--jobs <n>] [--timeout <seconds>]
+                  [--allow-unchecked]
+options -v and -vv write debug and trace output of the days to standard error,
+and --year <year> selects the year (the latest year by default)";
+
+type Args<'a> = Peekable<slice::Iter<'a, String>>;
+
+/// Output format of the `run` command.
+enum Format {
+    Table,
+    Json,
+}
+
+/// Options for the `run` command.
+struct RunOptions {
+    all: bool,
+    days: Vec<&'static Day>,
+    parts: Vec<Part>,
+    source: Source,
+}
+
+/// Get the value of an option.
+fn option_value<'a>(option: &str, iter: &mut Args<'a>) -> Result<&'a String, String> {
+    iter.next()
+        .ok_or_else(|| format!("{} requires an argument", option))
+}
+
+/// Parse the number of days solved at the same time.
+fn parse_jobs(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| format!("bad number of jobs '{}'", value))
+}
+
+/// Parse the time limit of a day in seconds.
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|&s| s > 0.0 && s.is_finite())
+        .map(Duration::from_secs_f64)
+        .ok_or_else(|| format!("bad timeout '{}'", value))
+}
+
+/// Parse the options selecting days, parts, and input. Options that
+/// are specific to a command are passed to `extra`, which returns
+/// `true` if it handled the option.
+fn parse_run_options<F>(year: u32, args: &[String], mut extra: F) -> Result<RunOptions, String>
+where
+    F: FnMut(&str, &mut Args) -> Result<bool, String>,
+{
+    let mut all = false;
+    let mut day = None;
+    let mut parts = vec![Part::One, Part::Two];
+    let mut source = Source::Search;
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--example" => {
+                let number = iter.peek().and_then(|arg| arg.parse().ok());
+                if number.is_some() {
+                    iter.next();
+                }
+                source = Source::Example(number.unwrap_or(1));
+            }
+            "--part" => parts = vec![option_value(arg, &mut iter)?.parse()?],
+            "--input" => source = Source::from_arg(Some(option_value(arg, &mut iter)?)),
+            _ if extra(arg, &mut iter)? => {}
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ if day.is_none() => {
+                let number: u32 = arg
+                    .parse()
+                    .map_err(|_| format!("bad day number '{}'", arg))?;
+                day = Some(find_day(year, number)?);
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let days = match (all, day) {
+        (true, None) => match source {
+            Source::Path(_) | Source::Stdin => {
+                return Err("--input cannot be used with --all".to_string())
+            }
+            Source::Search | Source::Example(_) => registry::days_of(year).collect(),
+        },
+        (false, Some(day)) => vec![day],
+        (true, Some(_)) => return Err("give either a day or --all, not both".to_string()),
+        (false, None) => return Err("no day given".to_string()),
+    };
+
+    Ok(RunOptions {
+        all,
+        days,
+        parts,
+        source,
+    })
+}
+
+/// Run the days and print the answers. Returns `true` if all days
+/// were solved.
+fn run(year: u32, args: &[String]) -> Result<bool, String> {
+    let mut format = Format::Table;
+    let mut jobs = 1;
+    let mut timeout = None;
+    let mut use_cache = true;
+    let options = parse_run_options(year, args, |arg, iter| {
+        match arg {
+            "--no-cache" => use_cache = false,
+            "--format" => {
+                format = match option_value(arg, iter)?.as_str() {
+                    "table" => Format::Table,
+                    "json" => Format::Json,
+                    value => return Err(format!("unknown format '{}'", value)),
+                }
+            }
+            "--jobs" => jobs = parse_jobs(option_value(arg, iter)?)?,
+            "--timeout" => timeout = Some(parse_timeout(option_value(arg, iter)?)?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+
+    // Without a build hash, nothing can be cached safely.
+    let mut cache = match (use_cache, cache::build_hash()) {
+        (true, Ok(build)) => Some(Cache::open(&cache::default_path(), build)),
+        _ => None,
+    };
+    let mut inputs = vec![];
+    let mut hashes = HashMap::new();
+    let mut outcomes = vec![];
+    for &day in &options.days {
+        match input::load(year, day.number, &options.source) {
+            Ok(input) => {
+                let hash = cache::hash(input.text.as_bytes());
+                let cached: Option<Vec<Outcome>> = cache.as_ref().and_then(|cache| {
+                    options
+                        .parts
+                        .iter()
+                        .map(|&part| cache.get(day, part, hash))
+                        .collect()
+                });
+                match cached {
+                    Some(cached) => outcomes.extend(cached),
+                    None => {
+                        hashes.insert(day.number, hash);
+                        inputs.push((day, input));
+                    }
+                }
+            }
+            Err(InputError::NotFound(..))
+                if options.all && matches!(options.source, Source::Example(_)) => {}
+            Err(err) => outcomes.extend(
+                options
+                    .parts
+                    .iter()
+                    .map(|&part| Outcome::failed(day, part, AocError::new(err.to_string()))),
+            ),
+        }
+    }
+    let solved = runner::run_parallel(inputs, &options.parts, jobs, timeout);
+    if let Some(cache) = &mut cache {
+        for outcome in &solved {
+            cache.insert(outcome, hashes[&outcome.day]);
+        }
+        if let Err(err) = cache.save() {
+            eprintln!("cannot save the cache: {}", err);
+        }
+    }
+    outcomes.extend(solved);
+    // Stable, so the parts of each day stay in order.
+    outcomes.sort_by_key(|outcome| outcome.day);
+    match format {
+        Format::Table => runner::print_table(&outcomes),
+        Format::Json => println!("{}", runner::to_json(&outcomes).pretty()),
+    }
+    Ok(outcomes.iter().all(Outcome::is_ok))
+}
+
+/// Options for the `bench` command.
+struct BenchOptions {
+    iterations: usize,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+}
+
+fn parse_bench_options(year: u32, args: &[String]) -> Result<(RunOptions, BenchOptions), String> {
+    let mut bench = BenchOptions {
+        iterations: 10,
+        save: None,
+        baseline: None,
+        threshold: 10.0,
+    };
+    let options = parse_run_options(year, args, |arg, iter| {
+        match arg {
+            "--iterations" => {
+                let value = option_value(arg, iter)?;
+                bench.iterations = value
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| format!("bad number of iterations '{}'", value))?;
+            }
+            "--save" => bench.save = Some(option_value(arg, iter)?.into()),
+            "--baseline" => bench.baseline = Some(option_value(arg, iter)?.into()),
+            "--threshold" => {
+                let value = option_value(arg, iter)?;
+                bench.threshold = value
+                    .parse()
+                    .ok()
+                    .filter(|&x: &f64| x >= 0.0)
+                    .ok_or_else(|| format!("bad threshold '{}'", value))?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    Ok((options, bench))
+}
+
+/// Time the days and print the timings. Returns `true` if all days
+/// were solved and none of them regressed compared to the baseline.
+fn benchmark(year: u32, args: &[String]) -> Result<bool, String> {
+    let (options, bench) = parse_bench_options(year, args)?;
+    let baseline: Vec<Timing> = match &bench.baseline {
+        Some(path) => bench::load(path).map_err(|err| err.to_string())?,
+        None => vec![],
+    };
+
+    let mut ok = true;
+    let mut timings = vec![];
+    for day in &options.days {
+        let result = input::load(year, day.number, &options.source);
+        let input = match result {
+            Ok(input) => input,
+            Err(InputError::NotFound(..))
+                if options.all && matches!(options.source, Source::Example(_)) =>
+            {
+                continue
+            }
+            Err(err) => {
+                eprintln!("day {}: {}", day.number, err);
+                ok = false;
+                continue;
+            }
+        };
+        match bench::bench(day, &input.text, &options.parts, bench.iterations) {
+            Ok(mut result) => timings.append(&mut result),
+            Err(err) => {
+                eprintln!("day {}: {}", day.number, err);
+                ok = false;
+            }
+        }
+    }
+
+    let regressions = bench::print_table(&timings, &baseline, bench.threshold / 100.0);
+    if let Some(path) = &bench.save {
+        bench::save(path, &timings).map_err(|err| err.to_string())?;
+    }
+    Ok(ok && regressions == 0)
+}
+
+/// Parse the arguments of commands that talk to the site. Returns
+/// the positional arguments and a client using the base URL given
+/// with `--base-url`, if any.
+fn parse_site_args(args: &[String]) -> Result<(Vec<&String>, Client), String> {
+    let mut positional = vec![];
+    let mut base_url = None;
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--base-url" => base_url = Some(option_value(arg, &mut iter)?),
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ => positional.push(arg),
+        }
+    }
+    Ok((positional, Client::from_env(base_url.map(String::as_str))))
+}
+
+/// Take the year given with `--year` out of the arguments, wherever
+/// it is given. Without it, the latest year is used.
+fn take_year(args: Vec<String>) -> Result<(u32, Vec<String>), String> {
+    let mut year = registry::latest_year();
+    let mut rest = vec![];
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--year" {
+            let value = iter.next().ok_or("--year requires an argument")?;
+            year = value
+                .parse()
+                .ok()
+                .filter(|&year| year >= 2015)
+                .ok_or_else(|| format!("bad year '{}'", value))?;
+        } else {
+            rest.push(arg);
+        }
+    }
+    Ok((year, rest))
+}
+
+/// Look up a solved day of a year.
+fn find_day(year: u32, number: u32) -> Result<&'static Day, String> {
+    registry::find(year, number).ok_or_else(|| format!("day {} of {} is not solved", number, year))
+}
+
+fn parse_day(arg: &str) -> Result<u32, String> {
+    arg.parse()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("bad day number '{}'", arg))
+}
+
+/// Download the input of a day unless it is already there.
+fn fetch(year: u32, args: &[String]) -> Result<bool, String> {
+    let (args, client) = parse_site_args(args)?;
+    let day = match args[..] {
+        [day] => parse_day(day)?,
+        [] => return Err("no day given".to_string()),
+        _ => return Err(format!("unexpected argument '{}'", args[1])),
+    };
+    match fetch::fetch(&client, &Resolver::from_env(), year, day) {
+        Ok(Fetched::Cached(path)) => println!("day {}: already in {}", day, path.display()),
+        Ok(Fetched::Downloaded(path)) => println!("day {}: downloaded to {}", day, path.display()),
+        Err(err) => {
+            eprintln!("day {}: {}", day, err);
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Solve a part of a day and submit the answer. Returns `true` if the
+/// answer was correct.
+fn submit(year: u32, args: &[String]) -> Result<bool, String> {
+    let (args, client) = parse_site_args(args)?;
+    let (number, part) = match args[..] {
+        [day, part] => (parse_day(day)?, part.parse::<Part>()?),
+        _ => return Err("expected a day and a part".to_string()),
+    };
+    let day = find_day(year, number)?;
+
+    let resolver = Resolver::from_env();
+    let answer = match resolver.resolve(year, number, &Source::Search) {
+        Ok(input) => runner::run_input(day, &input, &[part]).remove(0).result,
+        Err(err) => Err(AocError::new(err.to_string())),
+    };
+    let result = answer.and_then(|answer| {
+        let dir = resolver.store_dir(year);
+        let verdict = submit::submit(&client, &dir, year, number, part, &answer)?;
+        Ok((answer, verdict))
+    });
+    match result {
+        Ok((answer, verdict)) => {
+            println!("day {} part {}: {} is {}", number, part, answer, verdict);
+            Ok(verdict == Verdict::Correct)
+        }
+        Err(err) => {
+            eprintln!("day {} part {}: {}", number, part, err);
+            Ok(false)
+        }
+    }
+}
+
+/// Add the files for a new day to the source tree.
+fn new(year: u32, args: &[String]) -> Result<bool, String> {
+    let mut day = None;
+    let mut title = None;
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--title" => title = Some(option_value(arg, &mut iter)?.as_str()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    let day = day.ok_or("no day given")?;
+    match scaffold::create(&PathBuf::from(env!("CARGO_MANIFEST_DIR")), year, day, title) {
+        Ok(paths) => {
+            for path in paths {
+                println!("day {}: wrote {}", day, path.display());
+            }
+            Ok(true)
+        }
+        Err(err) => {
+            eprintln!("day {}: {}", day, err);
+            Ok(false)
+        }
+    }
+}
+
+/// Solve a day again whenever its input or examples change.
+fn watch(year: u32, args: &[String]) -> Result<bool, String> {
+    let mut day = None;
+    let mut interval = Duration::from_secs(1);
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--interval" => {
+                let value = option_value(arg, &mut iter)?;
+                let seconds = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|&s| s > 0.0 && s.is_finite())
+                    .ok_or_else(|| format!("bad interval '{}'", value))?;
+                interval = Duration::from_secs_f64(seconds);
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ if day.is_none() => {
+                day = Some(find_day(year, parse_day(arg)?)?);
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    let day = day.ok_or("no day given")?;
+    let mut first = true;
+    watch::watch(day, &Resolver::from_env(), interval, |lines| {
+        if !first {
+            println!();
+        }
+        first = false;
+        println!("day {}:", day.number);
+        if lines.is_empty() {
+            println!("  no answers changed");
+        }
+        for line in lines {
+            println!("  {}", line);
+        }
+    })
+}
+
+/// Print generated input for a day.
+fn gen(year: u32, args: &[String]) -> Result<bool, String> {
+    let mut day = None;
+    let mut size = None;
+    let mut seed = 0;
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--size" => {
+                let value = option_value(arg, &mut iter)?;
+                size = Some(value.parse().map_err(|_| format!("bad size '{}'", value))?);
+            }
+            "--seed" => {
+                let value = option_value(arg, &mut iter)?;
+                seed = value.parse().map_err(|_| format!("bad seed '{}'", value))?;
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    let day = day.ok_or("no day given")?;
+    let generator = generate::find(year, day)
+        .ok_or_else(|| format!("no generator for day {} of {}", day, year))?;
+    match generator.generate(size, seed) {
+        Ok(text) => {
+            print!("{}", text);
+            Ok(true)
+        }
+        Err(err) => {
+            eprintln!("day {}: {}", day, err);
+            Ok(false)
+        }
+    }
+}
+
+/// Solve all days and write the site.
+fn report(year: u32, args: &[String]) -> Result<bool, String> {
+    let mut out = PathBuf::from("site");
+    let mut format = report::Format::Html;
+    let mut source_url = None;
+    let mut jobs = 1;
+    let mut timeout = None;
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--out" => out = option_value(arg, &mut iter)?.into(),
+            "--format" => {
+                format = match option_value(arg, &mut iter)?.as_str() {
+                    "html" => report::Format::Html,
+                    "markdown" => report::Format::Markdown,
+                    value => return Err(format!("unknown format '{}'", value)),
+                }
+            }
+            "--source-url" => source_url = Some(option_value(arg, &mut iter)?),
+            "--jobs" => jobs = parse_jobs(option_value(arg, &mut iter)?)?,
+            "--timeout" => timeout = Some(parse_timeout(option_value(arg, &mut iter)?)?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let mut inputs = vec![];
+    let mut outcomes = vec![];
+    for day in registry::days_of(year) {
+        match input::load(year, day.number, &Source::Search) {
+            Ok(input) => inputs.push((day, input)),
+            Err(err) => outcomes.extend(
+                [Part::One, Part::Two]
+                    .iter()
+                    .map(|&part| Outcome::failed(day, part, AocError::new(err.to_string()))),
+            ),
+        }
+    }
+    outcomes.extend(runner::run_parallel(
+        inputs,
+        &[Part::One, Part::Two],
+        jobs,
+        timeout,
+    ));
+
+    // Days that are not solved yet still get a page with their text.
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut pages = vec![];
+    for day in 1..=25 {
+        let file = registry::source_path(year, day);
+        let source = match fs::read_to_string(root.join(&file)) {
+            Ok(source) => source,
+            Err(_) => continue,
+        };
+        let link = match source_url {
+            Some(url) => format!("{}/{}", url.trim_end_matches('/'), file.display()),
+            None => format!("file://{}", root.join(&file).display()),
+        };
+        let (ours, rest) = outcomes.into_iter().partition(|outcome| outcome.day == day);
+        outcomes = rest;
+        pages.push(Page::new(day, &source, ours, link));
+    }
+
+    match report::write_site(&out, year, &pages, format) {
+        Ok(paths) => {
+            println!("wrote {} pages to {}", paths.len(), out.display());
+            Ok(true)
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            Ok(false)
+        }
+    }
+}
+
+/// Answer requests to solve parts over HTTP.
+fn serve(year: u32, args: &[String]) -> Result<bool, String> {
+    let mut port: u16 = 2020;
+    let mut bind = "127.0.0.1";
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--port" => {
+                let value = option_value(arg, &mut iter)?;
+                port = value.parse().map_err(|_| format!("bad port '{}'", value))?;
+            }
+            "--bind" => bind = option_value(arg, &mut iter)?,
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    let listener = match TcpListener::bind((bind, port)) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("cannot listen on {}:{}: {}", bind, port, err);
+            return Ok(false);
+        }
+    };
+    if let Ok(address) = listener.local_addr() {
+        println!("listening on http://{}", address);
+    }
+    if let Err(err) = serve::serve(&listener, year) {
+        eprintln!("{}", err);
+    }
+    Ok(false)
+}
+
+/// Solve a day for every input in a directory and check the answers.
+fn verify(year: u32, args: &[String]) -> Result<bool, String> {
+    let mut day = None;
+    let mut dir = None;
+    let mut parts = vec![Part::One, Part::Two];
+    let mut jobs = 1;
+    let mut timeout = None;
+    let mut allow_unchecked = false;
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--allow-unchecked" => allow_unchecked = true,
+            "--inputs" => dir = Some(PathBuf::from(option_value(arg, &mut iter)?)),
+            "--part" => parts = vec![option_value(arg, &mut iter)?.parse()?],
+            "--jobs" => jobs = parse_jobs(option_value(arg, &mut iter)?)?,
+            "--timeout" => timeout = Some(parse_timeout(option_value(arg, &mut iter)?)?),
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ if day.is_none() => {
+                day = Some(find_day(year, parse_day(arg)?)?);
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    let day = day.ok_or("no day given")?;
+    let dir = dir.ok_or("no directory of inputs given with --inputs")?;
+    let paths = match verify::inputs(&dir) {
+        Ok(paths) if paths.is_empty() => {
+            eprintln!("{}: no inputs", dir.display());
+            return Ok(false);
+        }
+        Ok(paths) => paths,
+        Err(err) => {
+            eprintln!("{}: {}", dir.display(), err);
+            return Ok(false);
+        }
+    };
+    let rows = verify::verify(day, &paths, &parts, jobs, timeout);
+    print!("{}", verify::format(&rows, &parts));
+    let failed = rows
+        .iter()
+        .filter(|row| !row.is_ok(allow_unchecked))
+        .count();
+    println!("\n{} of {} inputs failed", failed, rows.len());
+    Ok(failed == 0)
+}
+
+fn main() {
+    let args = trace::take_verbosity(env::args().skip(1).collect());
+    let result = take_year(args).and_then(|(year, args)| match args.first().map(String::as_str) {
+        Some("run") => run(year, &args[1..]),
+        Some("bench") => benchmark(year, &args[1..]),
+        Some("fetch") => fetch(year, &args[1..]),
+        Some("submit") => submit(year, &args[1..]),
+        Some("new") => new(year, &args[1..]),
+        Some("watch") => watch(year, &args[1..]),
+        Some("gen") => gen(year, &args[1..]),
+        Some("report") => report(year, &args[1..]),
+        Some("serve") => serve(year, &args[1..]),
+        Some("verify") => verify(year, &args[1..]),
+        Some("help") | Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            Ok(true)
+        }
+        Some(cmd) => Err(format!("unknown command '{}'", cmd)),
+        None => Err("no command given".to_string()),
+    });
+
+    match result {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(msg) => {
+            eprintln!("aoc: {}", msg);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    }
+}
@@ -1,107 +1,12 @@
-//! Day 1
-//!
-//! After saving Christmas five years in a row, you've decided to take
-//! a vacation at a nice resort on a tropical island. Surely,
-//! Christmas will go on without you.
-//!
-//! The tropical island has its own currency and is entirely
-//! cash-only. The gold coins used there have a little picture of a
-//! starfish; the locals just call them stars. None of the currency
-//! exchanges seem to have heard of them, but somehow, you'll need to
-//! find fifty of these coins by the time you arrive so you can pay
-//! the deposit on your room.
-//!
-//! To save your vacation, you need to get all fifty stars by December
-//! 25th.
-//!
-//! Collect stars by solving puzzles. Two puzzles will be made
-//! available on each day in the Advent calendar; the second puzzle is
-//! unlocked when you complete the first. Each puzzle grants one
-//! star. Good luck!
-//!
-//! Before you leave, the Elves in accounting just need you to fix
-//! your expense report (your puzzle input); apparently, something
-//! isn't quite adding up.
-//!
-//! Specifically, they need you to find the two entries that sum to
-//! 2020 and then multiply those two numbers together.
-//!
-//! For example, suppose your expense report contained the following:
-//!
-//!     1721
-//!     979
-//!     366
-//!     299
-//!     675
-//!     1456
-//!
-//! In this list, the two entries that sum to 2020 are 1721 and
-//! 299. Multiplying them together produces 1721 * 299 = 514579, so
-//! the correct answer is 514579.
-//!
-//! Of course, your expense report is much larger. Find the two
-//! entries that sum to 2020; what do you get if you multiply them
-//! together?
-//!
-//! Your puzzle answer was 692916.
-//!
-//! # Part Two
-//!
-//! The Elves in accounting are thankful for your help; one of them
-//! even offers you a starfish coin they had left over from a past
-//! vacation. They offer you a second one if you can find three
-//! numbers in your expense report that meet the same criteria.
-//!
-//! Using the above example again, the three entries that sum to 2020
-//! are 979, 366, and 675. Multiplying them together produces the
-//! answer, 241861950.
-//!
-//! In your expense report, what is the product of the three entries that sum to 2020?
-//!
-//! Your puzzle answer was 289270976.
-
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    iter::repeat,
-    path::PathBuf,
-};
+use adventofcode::{day01::Day01, input, Part, Solver};
+use std::error::Error;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push("src/bin/aoc1.dat");
-    let file = File::open(path)?;
-    let input = BufReader::new(file);
-    let nums: Vec<u32> = input
-        .lines()
-        .map(|line| line.unwrap().trim().parse().unwrap())
-        .collect();
-
-    // Part 1
-    let candidates: Vec<_> = nums
-        .iter()
-        .enumerate()
-        .flat_map(|(i, x)| repeat(x).zip(&nums[(i + 1)..]))
-        .filter(|(&x, &y)| x + y == 2020)
-        .collect();
-
-    for (x, y) in candidates {
-        println!("Part 1: {} * {} = {}", x, y, x * y);
-    }
-
-    // Part 2
-    let candidates: Vec<_> = nums
-        .iter()
-        .enumerate()
-        .flat_map(|(i, x)| repeat(x).zip(&nums[(i + 1)..]).enumerate())
-        .flat_map(|(j, (x, y))| repeat((x, y)).zip(&nums[(j + 1)..]))
-        .filter(|((&x, &y), &z)| x + y + z == 2020)
-        .collect();
-
-    for ((x, y), z) in candidates {
-        println!("Part 2: {} * {} * {} = {}", x, y, z, x * y * z);
-    }
-
+    let file = input::from_args(2020, 1)?;
+    let input = Day01
+        .parse_input(&file.text)
+        .map_err(|err| err.in_file(&file.origin))?;
+    println!("Part 1: {}", Day01.solve_part(input.as_ref(), Part::One)?);
+    println!("Part 2: {}", Day01.solve_part(input.as_ref(), Part::Two)?);
     Ok(())
 }
@@ -1,71 +1,12 @@
-#![feature(iterator_fold_self)]
-
-use std::{collections::HashSet, error::Error, fs::read_to_string, path::PathBuf};
-
-fn part1(forms: &[Vec<&str>]) -> usize {
-    forms
-        .iter()
-        .map(|group| {
-            let result: HashSet<_> = group
-                .iter()
-                .map(|s| s.chars().collect::<HashSet<_>>())
-                .fold_first(|a, s| a.union(&s).cloned().collect())
-                .unwrap();
-            result.len()
-        })
-        .sum::<usize>()
-}
-
-fn part2(forms: &[Vec<&str>]) -> usize {
-    forms
-        .iter()
-        .map(|group| {
-            let result: HashSet<_> = group
-                .iter()
-                .map(|s| s.chars().collect::<HashSet<_>>())
-                .fold_first(|a, s| a.intersection(&s).cloned().collect())
-                .unwrap();
-            result.len()
-        })
-        .sum::<usize>()
-}
+use adventofcode::{day06::Day06, input, Part, Solver};
+use std::error::Error;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push("src/bin/aoc6.dat");
-    let contents = read_to_string(path)?;
-    let forms: Vec<Vec<&str>> = contents
-        .split("\n\n")
-        .map(|group| group.lines().collect::<Vec<_>>())
-        .collect();
-
-    // Part 1
-    println!("part 1: {:?}", part1(&forms));
-
-    // Part 2
-    println!("part 2: {:?}", part2(&forms));
+    let file = input::from_args(2020, 6)?;
+    let input = Day06
+        .parse_input(&file.text)
+        .map_err(|err| err.in_file(&file.origin))?;
+    println!("Part 1: {}", Day06.solve_part(input.as_ref(), Part::One)?);
+    println!("Part 2: {}", Day06.solve_part(input.as_ref(), Part::Two)?);
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part1_examples() {
-        assert_eq!(part1(&vec![vec!["abc"]]), 3);
-        assert_eq!(part1(&vec![vec!["a", "b", "c"]]), 3);
-        assert_eq!(part1(&vec![vec!["ab", "ac"]]), 3);
-        assert_eq!(part1(&vec![vec!["a", "a", "a", "a"]]), 1);
-        assert_eq!(part1(&vec![vec!["b"]]), 1);
-    }
-
-    #[test]
-    fn part2_examples() {
-        assert_eq!(part2(&vec![vec!["abc"]]), 3);
-        assert_eq!(part2(&vec![vec!["a", "b", "c"]]), 0);
-        assert_eq!(part2(&vec![vec!["ab", "ac"]]), 1);
-        assert_eq!(part2(&vec![vec!["a", "a", "a", "a"]]), 1);
-        assert_eq!(part2(&vec![vec!["b"]]), 1);
-    }
-}
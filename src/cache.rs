@@ -0,0 +1,251 @@
+//! Cache of answers.
+//!
+//! Solving all days again takes a while, mostly for the slow ones like
+//! the second part of day 15, so the answers are kept in a cache. An
+//! answer is found again by year, day, part, and the hash of the
+//! input, and only for the same build of the solvers, so changing the
+//! input or rebuilding the binary solves the part again.
+//!
+//! The cache is a JSON file, `target/aoc-cache.json` unless another is
+//! given in `AOC_CACHE`. Only the answers of one build are kept: when
+//! the build changes, the old answers are dropped.
+//!
+//! ```text
+//! {
+//!   "build": "5f0d6c2b8e4a9137",
+//!   "answers": [
+//!     {
+//!       "year": 2020,
+//!       "day": 15,
+//!       "part": 2,
+//!       "input": "c3e1a08f7b2d5946",
+//!       "answer": 1505722,
+//!       "duration_ns": 1843297163
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! The hashes are FNV-1a, which needs no dependencies and is the same
+//! on every platform. It is not a cryptographic hash, but it only has
+//! to tell inputs and builds apart, not withstand an attacker.
+
+use crate::{
+    error::{AocError, Result},
+    json::{self, Value},
+    registry::Day,
+    runner::Outcome,
+    Answer, Part,
+};
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    env,
+    fs::{self, read_to_string, write},
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Environment variable with the path of the cache file.
+pub const CACHE_VAR: &str = "AOC_CACHE";
+
+/// Hash of some bytes.
+pub fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Hash of the running binary, which changes whenever the solvers are
+/// built again with changes.
+pub fn build_hash() -> io::Result<u64> {
+    Ok(hash(&fs::read(env::current_exe()?)?))
+}
+
+/// Path of the cache file: `AOC_CACHE` if set, and `aoc-cache.json`
+/// in the target directory of the source tree otherwise.
+pub fn default_path() -> PathBuf {
+    match env::var_os(CACHE_VAR) {
+        Some(path) => PathBuf::from(path),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("target/aoc-cache.json"),
+    }
+}
+
+/// What an answer is cached by: year, day, part, and input hash.
+type Key = (u32, u32, Part, u64);
+
+/// Cached answers of one build.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    path: PathBuf,
+    build: u64,
+    answers: BTreeMap<Key, (Answer, Duration)>,
+}
+
+impl Cache {
+    /// Open the cache file at `path` for the given build. A file that
+    /// is missing, cannot be read, or is of another build gives an
+    /// empty cache, which replaces the file when saved.
+    pub fn open(path: &Path, build: u64) -> Cache {
+        let answers = read_to_string(path)
+            .ok()
+            .and_then(|text| json::parse(&text).ok())
+            .filter(|value| value.get("build").and_then(Value::as_str) == Some(&hex(build)))
+            .and_then(|value| from_json(&value).ok())
+            .unwrap_or_default();
+        Cache {
+            path: path.to_path_buf(),
+            build,
+            answers,
+        }
+    }
+
+    /// Cached outcome of a part of a day for the input with the given
+    /// hash, if any.
+    pub fn get(&self, day: &Day, part: Part, input: u64) -> Option<Outcome> {
+        let (answer, duration) = self.answers.get(&(day.year, day.number, part, input))?;
+        Some(Outcome {
+            year: day.year,
+            day: day.number,
+            part,
+            result: Ok(answer.clone()),
+            duration: *duration,
+            timed_out: false,
+            cached: true,
+        })
+    }
+
+    /// Add the answer of an outcome for the input with the given hash.
+    /// Failed and cached outcomes are left out.
+    pub fn insert(&mut self, outcome: &Outcome, input: u64) {
+        if let (Ok(answer), false) = (&outcome.result, outcome.cached) {
+            self.answers.insert(
+                (outcome.year, outcome.day, outcome.part, input),
+                (answer.clone(), outcome.duration),
+            );
+        }
+    }
+
+    /// Write the cache to its file.
+    pub fn save(&self) -> Result<()> {
+        let io_error = |err: io::Error| AocError::new(format!("{}: {}", self.path.display(), err));
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        write(&self.path, self.to_json().pretty() + "\n").map_err(io_error)
+    }
+
+    fn to_json(&self) -> Value {
+        let answers = self
+            .answers
+            .iter()
+            .map(|(&(year, day, part, input), (answer, duration))| {
+                let answer = match answer {
+                    Answer::Number(n) => Value::Int(*n),
+                    Answer::Text(s) => Value::from(s.as_str()),
+                };
+                let nanos = i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX);
+                Value::object(vec![
+                    ("year", Value::from(year)),
+                    ("day", Value::from(day)),
+                    ("part", Value::from(part.number())),
+                    ("input", Value::from(hex(input))),
+                    ("answer", answer),
+                    ("duration_ns", Value::Int(nanos)),
+                ])
+            })
+            .collect();
+        Value::object(vec![
+            ("build", Value::from(hex(self.build))),
+            ("answers", Value::Array(answers)),
+        ])
+    }
+}
+
+fn hex(hash: u64) -> String {
+    format!("{:016x}", hash)
+}
+
+fn from_json(value: &Value) -> Result<BTreeMap<Key, (Answer, Duration)>> {
+    let answers = value
+        .get("answers")
+        .and_then(Value::as_array)
+        .ok_or("cache has no answers")?;
+    answers
+        .iter()
+        .map(|entry| {
+            let field = |key| {
+                entry
+                    .get(key)
+                    .ok_or_else(|| AocError::bad("missing field", key))
+            };
+            let int = |key| {
+                field(key)?
+                    .as_i64()
+                    .ok_or_else(|| AocError::bad("bad field", key))
+            };
+            let part = match int("part")? {
+                1 => Part::One,
+                2 => Part::Two,
+                _ => return Err(AocError::bad("bad field", "part")),
+            };
+            let input = field("input")?
+                .as_str()
+                .and_then(|text| u64::from_str_radix(text, 16).ok())
+                .ok_or_else(|| AocError::bad("bad field", "input"))?;
+            let answer = match field("answer")? {
+                Value::Int(n) => Answer::Number(*n),
+                Value::String(s) => Answer::Text(s.clone()),
+                _ => return Err(AocError::bad("bad field", "answer")),
+            };
+            let duration = Duration::from_nanos(int("duration_ns")?.max(0) as u64);
+            let key = (int("year")? as u32, int("day")? as u32, part, input);
+            Ok((key, (answer, duration)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use std::process;
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(hash(b"1\n2\n"), hash(b"2\n1\n"));
+    }
+
+    #[test]
+    fn test_cache() {
+        let path = env::temp_dir().join(format!("aoc-cache-test-{}.json", process::id()));
+        let day = registry::find(2020, 15).unwrap();
+        let input = hash(b"0,3,6\n");
+        let outcome = Outcome {
+            result: Ok(Answer::Number(175594)),
+            duration: Duration::from_millis(1500),
+            ..Outcome::failed(day, Part::Two, AocError::new("not solved"))
+        };
+
+        let mut cache = Cache::open(&path, 1);
+        assert!(cache.get(day, Part::Two, input).is_none());
+        cache.insert(&outcome, input);
+        cache.insert(&Outcome::failed(day, Part::One, AocError::new("no")), input);
+        cache.save().unwrap();
+
+        let cache = Cache::open(&path, 1);
+        let cached = cache.get(day, Part::Two, input).unwrap();
+        assert!(cached.cached);
+        assert_eq!(cached.result, Ok(Answer::Number(175594)));
+        assert_eq!(cached.duration, Duration::from_millis(1500));
+        assert!(cache.get(day, Part::One, input).is_none());
+        assert!(cache.get(day, Part::Two, hash(b"1,3,2\n")).is_none());
+
+        // Another build does not see the answers of the first.
+        assert!(Cache::open(&path, 2).get(day, Part::Two, input).is_none());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,217 @@
+//! Property-based checks.
+//!
+//! A property is checked on many generated values, starting small and
+//! growing. When it fails, the value is shrunk to a minimal
+//! counterexample: smaller values are tried, as given by [`Shrink`],
+//! for as long as the property keeps failing. This is mostly used to
+//! check an optimized solver against a straightforward reference
+//! implementation with [`cross_check`].
+//!
+//! The values are generated from a fixed seed, so failures can be
+//! reproduced. Another seed can be given in `AOC_CHECK_SEED`.
+
+use crate::{random::Rng, runner::guard, AocError};
+use std::{env, fmt::Debug};
+
+/// Environment variable with the seed for generating values.
+pub const SEED_VAR: &str = "AOC_CHECK_SEED";
+
+const DEFAULT_SEED: u64 = 2020;
+
+/// Largest size passed to the generators.
+const MAX_SIZE: usize = 50;
+
+/// Values that can be made smaller.
+pub trait Shrink: Clone {
+    /// Values that are smaller than this one, simplest first.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! shrink_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Shrink for $t {
+                fn shrink(&self) -> Vec<$t> {
+                    let mut smaller = vec![];
+                    if *self > 0 {
+                        smaller.push(0);
+                    }
+                    // Approach the value in halving steps.
+                    let mut step = *self / 2;
+                    while step > 0 {
+                        smaller.push(*self - step);
+                        step /= 2;
+                    }
+                    smaller
+                }
+            }
+        )*
+    };
+}
+
+shrink_unsigned!(u32, u64, usize);
+
+impl Shrink for i32 {
+    fn shrink(&self) -> Vec<i32> {
+        let magnitude = self.unsigned_abs().shrink();
+        let mut smaller: Vec<i32> = magnitude.iter().map(|&m| m as i32).collect();
+        if *self < 0 {
+            // Positive values are simpler.
+            smaller.extend(self.checked_neg());
+            smaller.extend(magnitude.iter().filter(|&&m| m > 0).map(|&m| -(m as i32)));
+        }
+        smaller
+    }
+}
+
+impl<T: Shrink> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Vec<T>> {
+        let mut smaller = vec![];
+        // Remove chunks, halving their size.
+        let mut chunk = self.len();
+        while chunk > 0 {
+            for start in (0..self.len()).step_by(chunk) {
+                let mut shorter = self[..start].to_vec();
+                shorter.extend_from_slice(&self[(start + chunk).min(self.len())..]);
+                smaller.push(shorter);
+            }
+            chunk /= 2;
+        }
+        // Shrink single elements.
+        for (index, value) in self.iter().enumerate() {
+            for element in value.shrink() {
+                let mut simpler = self.clone();
+                simpler[index] = element;
+                smaller.push(simpler);
+            }
+        }
+        smaller
+    }
+}
+
+impl<A: Shrink, B: Shrink> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<(A, B)> {
+        let firsts = self.0.shrink().into_iter().map(|a| (a, self.1.clone()));
+        let seconds = self.1.shrink().into_iter().map(|b| (self.0.clone(), b));
+        firsts.chain(seconds).collect()
+    }
+}
+
+/// Check the property on a value, turning a panic into a failure.
+fn holds<T, P>(property: &P, value: &T) -> Result<(), String>
+where
+    P: Fn(&T) -> Result<(), String>,
+{
+    guard(|| property(value).map_err(AocError::from)).map_err(|err| err.to_string())
+}
+
+/// Shrink a value for which the property fails, returning the
+/// smallest value found that still fails with its failure.
+pub fn shrink<T, P>(mut value: T, mut failure: String, property: P) -> (T, String)
+where
+    T: Shrink,
+    P: Fn(&T) -> Result<(), String>,
+{
+    'shrinking: loop {
+        for candidate in value.shrink() {
+            if let Err(err) = holds(&property, &candidate) {
+                value = candidate;
+                failure = err;
+                continue 'shrinking;
+            }
+        }
+        return (value, failure);
+    }
+}
+
+/// Seed from `AOC_CHECK_SEED`, if set, or the default seed.
+fn seed() -> u64 {
+    env::var(SEED_VAR)
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(DEFAULT_SEED)
+}
+
+/// Check that the property holds for `cases` values made by
+/// `generate`, which is given a size that grows with each case.
+/// Panics with a minimal counterexample if it does not.
+pub fn check<T, G, P>(name: &str, cases: usize, mut generate: G, property: P)
+where
+    T: Shrink + Debug,
+    G: FnMut(&mut Rng, usize) -> T,
+    P: Fn(&T) -> Result<(), String>,
+{
+    let seed = seed();
+    let mut rng = Rng::new(seed);
+    for case in 0..cases {
+        let size = 1 + case * MAX_SIZE / cases.max(1);
+        let value = generate(&mut rng, size);
+        if let Err(failure) = holds(&property, &value) {
+            let (value, failure) = shrink(value, failure, &property);
+            panic!(
+                "{} failed on case {} with seed {}: {}\nminimal counterexample: {:?}",
+                name, case, seed, failure, value
+            );
+        }
+    }
+}
+
+/// Check that `optimized` gives the same result as `reference` for
+/// `cases` generated values.
+pub fn cross_check<T, R, G, F, O>(name: &str, cases: usize, generate: G, reference: F, optimized: O)
+where
+    T: Shrink + Debug,
+    R: PartialEq + Debug,
+    G: FnMut(&mut Rng, usize) -> T,
+    F: Fn(&T) -> R,
+    O: Fn(&T) -> R,
+{
+    check(name, cases, generate, |value| {
+        let want = reference(value);
+        let got = optimized(value);
+        if want == got {
+            Ok(())
+        } else {
+            Err(format!("expected {:?}, got {:?}", want, got))
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+
+    #[test]
+    fn test_shrink() {
+        assert_eq!(0u32.shrink(), vec![]);
+        assert_eq!(10u32.shrink(), vec![0, 5, 8, 9]);
+        assert_eq!((-4i32).shrink(), vec![0, 2, 3, 4, -2, -3]);
+        assert_eq!(
+            vec![1u32, 2].shrink(),
+            vec![vec![], vec![2], vec![1], vec![0, 2], vec![1, 0], vec![1, 1]]
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let failure = panic::catch_unwind(|| {
+            cross_check(
+                "max",
+                1000,
+                |rng, size| {
+                    (0..size)
+                        .map(|_| rng.below(1000) as u32)
+                        .collect::<Vec<u32>>()
+                },
+                |values| values.iter().max().cloned(),
+                |values| values.iter().take(5).max().cloned(),
+            )
+        })
+        .unwrap_err();
+        assert_eq!(
+            failure.downcast_ref::<String>().unwrap().split('\n').nth(1),
+            Some("minimal counterexample: [0, 0, 0, 0, 0, 1]")
+        );
+    }
+}
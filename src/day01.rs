@@ -0,0 +1,169 @@
+//! Day 1
+//!
+//! After saving Christmas five years in a row, you've decided to take
+//! a vacation at a nice resort on a tropical island. Surely,
+//! Christmas will go on without you.
+//!
+//! The tropical island has its own currency and is entirely
+//! cash-only. The gold coins used there have a little picture of a
+//! starfish; the locals just call them stars. None of the currency
+//! exchanges seem to have heard of them, but somehow, you'll need to
+//! find fifty of these coins by the time you arrive so you can pay
+//! the deposit on your room.
+//!
+//! To save your vacation, you need to get all fifty stars by December
+//! 25th.
+//!
+//! Collect stars by solving puzzles. Two puzzles will be made
+//! available on each day in the Advent calendar; the second puzzle is
+//! unlocked when you complete the first. Each puzzle grants one
+//! star. Good luck!
+//!
+//! Before you leave, the Elves in accounting just need you to fix
+//! your expense report (your puzzle input); apparently, something
+//! isn't quite adding up.
+//!
+//! Specifically, they need you to find the two entries that sum to
+//! 2020 and then multiply those two numbers together.
+//!
+//! For example, suppose your expense report contained the following:
+//!
+//! ```text
+//! 1721
+//! 979
+//! 366
+//! 299
+//! 675
+//! 1456
+//! ```
+//!
+//! In this list, the two entries that sum to 2020 are 1721 and
+//! 299. Multiplying them together produces 1721 * 299 = 514579, so
+//! the correct answer is 514579.
+//!
+//! Of course, your expense report is much larger. Find the two
+//! entries that sum to 2020; what do you get if you multiply them
+//! together?
+//!
+//! Your puzzle answer was 692916.
+//!
+//! # Part Two
+//!
+//! The Elves in accounting are thankful for your help; one of them
+//! even offers you a starfish coin they had left over from a past
+//! vacation. They offer you a second one if you can find three
+//! numbers in your expense report that meet the same criteria.
+//!
+//! Using the above example again, the three entries that sum to 2020
+//! are 979, 366, and 675. Multiplying them together produces the
+//! answer, 241861950.
+//!
+//! In your expense report, what is the product of the three entries that sum to 2020?
+//!
+//! Your puzzle answer was 289270976.
+
+use crate::{error::Result, parse::parse_lines, random::Rng, Solution};
+use std::collections::HashSet;
+
+/// The total the entries have to sum to.
+const TOTAL: u32 = 2020;
+
+/// Product of two entries that sum to `total`. Of the pairs that do,
+/// the one whose second entry comes first is used.
+pub fn pair_product(nums: &[u32], total: u32) -> Option<u32> {
+    let mut seen = HashSet::new();
+    for &x in nums {
+        if let Some(y) = total.checked_sub(x).filter(|y| seen.contains(y)) {
+            return Some(x * y);
+        }
+        seen.insert(x);
+    }
+    None
+}
+
+/// Product of three entries that sum to `total`. Of the triples that
+/// do, the one whose last entry comes first is used, and then the one
+/// whose middle entry comes first.
+pub fn triple_product(nums: &[u32], total: u32) -> Option<u32> {
+    nums.iter().enumerate().find_map(|(k, &z)| {
+        let rest = total.checked_sub(z)?;
+        pair_product(&nums[..k], rest).map(|product| product * z)
+    })
+}
+
+/// Random expense report with `size` entries, which may or may not
+/// have entries summing to 2020.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<u32> {
+    (0..size)
+        .map(|_| rng.below(TOTAL as u64 + 1) as u32)
+        .collect()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(&self, text: &str) -> Result<Vec<u32>> {
+        parse_lines(text)
+    }
+
+    fn part1(&self, nums: &Vec<u32>) -> Result<u32> {
+        pair_product(nums, TOTAL).ok_or_else(|| "no two entries sum to 2020".into())
+    }
+
+    fn part2(&self, nums: &Vec<u32>) -> Result<u32> {
+        triple_product(nums, TOTAL).ok_or_else(|| "no three entries sum to 2020".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::cross_check;
+
+    /// Try all pairs, in the order of `pair_product`.
+    fn pair_product_naive(nums: &[u32], total: u32) -> Option<u32> {
+        (0..nums.len())
+            .flat_map(|j| (0..j).map(move |i| (i, j)))
+            .find(|&(i, j)| nums[i] + nums[j] == total)
+            .map(|(i, j)| nums[i] * nums[j])
+    }
+
+    /// Try all triples, in the order of `triple_product`.
+    fn triple_product_naive(nums: &[u32], total: u32) -> Option<u32> {
+        (0..nums.len())
+            .flat_map(|k| (0..k).flat_map(move |j| (0..j).map(move |i| (i, j, k))))
+            .find(|&(i, j, k)| nums[i] + nums[j] + nums[k] == total)
+            .map(|(i, j, k)| nums[i] * nums[j] * nums[k])
+    }
+
+    #[test]
+    fn test_example() {
+        let nums = vec![1721, 979, 366, 299, 675, 1456];
+        assert_eq!(pair_product(&nums, TOTAL), Some(514579));
+        assert_eq!(triple_product(&nums, TOTAL), Some(241861950));
+        // An entry cannot be used twice.
+        assert_eq!(triple_product(&[20, 1000], TOTAL), None);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        cross_check(
+            "day 1 part 1",
+            2000,
+            generate,
+            |nums| pair_product_naive(nums, TOTAL),
+            |nums| pair_product(nums, TOTAL),
+        );
+        cross_check(
+            "day 1 part 2",
+            2000,
+            generate,
+            |nums| triple_product_naive(nums, TOTAL),
+            |nums| triple_product(nums, TOTAL),
+        );
+    }
+}
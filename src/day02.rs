@@ -0,0 +1,75 @@
+//! Day 2: Password Philosophy
+
+use crate::{
+    error::{AocError, Result},
+    parse::parse_lines_with,
+    Solution,
+};
+use regex::Regex;
+
+#[derive(Debug)]
+pub struct Record {
+    first: usize,
+    second: usize,
+    letter: char,
+    passwd: String,
+}
+
+fn position(line: &str, token: &str) -> Result<usize> {
+    match token.parse() {
+        Ok(pos) if pos > 0 => Ok(pos),
+        _ => Err(AocError::bad("invalid position", token).at_token(line, token)),
+    }
+}
+
+fn readpw(regex: &Regex, line: &str) -> Result<Record> {
+    let caps = regex
+        .captures(line)
+        .ok_or_else(|| AocError::bad("invalid password entry", line).at_column(1))?;
+    Ok(Record {
+        first: position(line, caps.get(1).unwrap().as_str())?,
+        second: position(line, caps.get(2).unwrap().as_str())?,
+        letter: caps[3].chars().next().unwrap(),
+        passwd: caps[4].to_string(),
+    })
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Record>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Vec<Record>> {
+        let regex = Regex::new(r"(\d+)-(\d+) *(\w): *(.*)").unwrap();
+        parse_lines_with(text, |line| readpw(&regex, line))
+    }
+
+    fn part1(&self, records: &Vec<Record>) -> Result<usize> {
+        let valid = records
+            .iter()
+            .filter(|record| {
+                let count = record
+                    .passwd
+                    .chars()
+                    .filter(|&ch| ch == record.letter)
+                    .count();
+                record.first <= count && count <= record.second
+            })
+            .count();
+        Ok(valid)
+    }
+
+    fn part2(&self, records: &Vec<Record>) -> Result<usize> {
+        let valid = records
+            .iter()
+            .filter(|record| {
+                let first = record.passwd.chars().nth(record.first - 1) == Some(record.letter);
+                let second = record.passwd.chars().nth(record.second - 1) == Some(record.letter);
+                first != second
+            })
+            .count();
+        Ok(valid)
+    }
+}
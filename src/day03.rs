@@ -0,0 +1,79 @@
+//! Day 3: Toboggan Trajectory
+
+use crate::{
+    error::{AocError, Result},
+    Solution,
+};
+
+pub struct Map {
+    map: Vec<Vec<u8>>,
+}
+
+impl Map {
+    /// Read a map of open squares `.` and trees `#`.
+    pub fn new(text: &str) -> Result<Map> {
+        let mut map: Vec<Vec<u8>> = vec![];
+        for (lineno, line) in text.split_terminator('\n').enumerate() {
+            if let Some((col, ch)) = line.chars().enumerate().find(|(_, ch)| !".#".contains(*ch)) {
+                return Err(AocError::bad("unexpected character", &ch.to_string())
+                    .at_column(col + 1)
+                    .at_line(lineno + 1));
+            }
+            if let Some(first) = map.first() {
+                if first.len() != line.len() {
+                    return Err(AocError::new(format!(
+                        "row has length {}, expected {}",
+                        line.len(),
+                        first.len()
+                    ))
+                    .at_line(lineno + 1));
+                }
+            }
+            map.push(line.as_bytes().to_vec());
+        }
+        if map.is_empty() || map[0].is_empty() {
+            return Err(AocError::new("empty map"));
+        }
+        Ok(Map { map })
+    }
+
+    /// Number of trees hit when going `right` and `down` in each step
+    /// from the top left corner.
+    pub fn trees(&self, right: usize, down: usize) -> u32 {
+        let mut count = 0;
+        let mut row = down;
+        let mut col = right;
+        while row < self.map.len() {
+            if self.map[row][col] == 35 {
+                count += 1;
+            }
+            col = (col + right) % self.map[row].len();
+            row += down;
+        }
+        count
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Map;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(&self, text: &str) -> Result<Map> {
+        Map::new(text)
+    }
+
+    fn part1(&self, map: &Map) -> Result<u32> {
+        Ok(map.trees(3, 1))
+    }
+
+    fn part2(&self, map: &Map) -> Result<u32> {
+        let product = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+            .iter()
+            .map(|(right, down)| map.trees(*right, *down))
+            .product();
+        Ok(product)
+    }
+}
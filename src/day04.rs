@@ -0,0 +1,99 @@
+//! Day 4: Passport Processing
+
+use crate::{
+    error::{AocError, Result},
+    parse::paragraphs,
+    Solution,
+};
+use std::collections::{HashMap, HashSet};
+
+pub type Passport = HashMap<String, String>;
+
+const EXPECTED_FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+/// Read the passports, which are separated by empty lines.
+pub fn read_passports(contents: &str) -> Result<Vec<Passport>> {
+    paragraphs(contents)
+        .iter()
+        .map(|para| {
+            let lines = para.parse_lines_with(|line| {
+                line.split_whitespace()
+                    .map(|field| {
+                        let (key, value) = field.split_once(':').ok_or_else(|| {
+                            AocError::bad("field without value", field).at_token(line, field)
+                        })?;
+                        Ok((key.to_string(), value.to_string()))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })?;
+            Ok(lines.into_iter().flatten().collect())
+        })
+        .collect()
+}
+
+fn check_year(value: &str, min: u32, max: u32) -> bool {
+    match value.parse::<u32>() {
+        Ok(year) => value.len() == 4 && year >= min && year <= max,
+        Err(_) => false,
+    }
+}
+
+fn check_field(key: &str, value: &str) -> bool {
+    match key {
+        "byr" => check_year(value, 1920, 2002),
+        "iyr" => check_year(value, 2010, 2020),
+        "eyr" => check_year(value, 2020, 2030),
+        "hgt" if value.len() > 2 => {
+            let (val, sfx) = value.split_at(value.len() - 2);
+            match (val.parse::<u32>(), sfx) {
+                (Ok(num), "cm") => (150..=193).contains(&num),
+                (Ok(num), "in") => (59..=76).contains(&num),
+                (Err(_), _) | (Ok(_), _) => false,
+            }
+        }
+        "hgt" => false,
+        "hcl" => {
+            value.len() == 7
+                && value.starts_with('#')
+                && value.chars().skip(1).all(|ch| ch.is_ascii_hexdigit())
+        }
+        "ecl" => ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value),
+        "pid" => value.len() == 9 && value.chars().all(|ch| ch.is_ascii_digit()),
+        _ => true, // Just ignore the fields
+    }
+}
+
+/// Whether the passport has all fields except `cid`, which is optional.
+pub fn has_fields(passport: &Passport) -> bool {
+    let keys: HashSet<&str> = passport.keys().map(|key| key.as_str()).collect();
+    EXPECTED_FIELDS.iter().all(|field| keys.contains(field))
+}
+
+/// Whether the values of all fields of the passport are valid.
+pub fn valid_fields(passport: &Passport) -> bool {
+    passport.iter().all(|(key, value)| check_field(key, value))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Passport>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Vec<Passport>> {
+        read_passports(text)
+    }
+
+    fn part1(&self, passports: &Vec<Passport>) -> Result<usize> {
+        Ok(passports.iter().filter(|p| has_fields(p)).count())
+    }
+
+    fn part2(&self, passports: &Vec<Passport>) -> Result<usize> {
+        let valid = passports
+            .iter()
+            .filter(|p| has_fields(p) && valid_fields(p))
+            .count();
+        Ok(valid)
+    }
+}
@@ -0,0 +1,84 @@
+//! Day 5: Binary Boarding
+
+use crate::{
+    error::{AocError, Result},
+    parse::parse_lines_with,
+    Solution,
+};
+
+/// Seat ID of a boarding pass, which is the row times 8 plus the
+/// column.
+pub fn seat_id<S: Into<String>>(line: S) -> u32 {
+    // Read the bits as a binary number and split it into the parts
+    // afterwards.
+    let mut result: u32 = 0;
+    for ch in line.into().chars() {
+        result = (result << 1) | (ch == 'R' || ch == 'B') as u32;
+    }
+    8 * (result >> 3) + (result & 0x7)
+}
+
+/// Check that a boarding pass has seven row characters followed by
+/// three column characters.
+fn check_pass(line: &str) -> Result<()> {
+    for (col, ch) in line.chars().enumerate() {
+        let valid = if col < 7 { "FB" } else { "LR" };
+        if !valid.contains(ch) {
+            return Err(AocError::bad("unexpected character", &ch.to_string()).at_column(col + 1));
+        }
+    }
+    if line.len() != 10 {
+        return Err(AocError::bad("boarding pass is not 10 characters", line).at_column(1));
+    }
+    Ok(())
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(&self, text: &str) -> Result<Vec<u32>> {
+        let mut seats = parse_lines_with(text, |line| {
+            check_pass(line)?;
+            Ok(seat_id(line))
+        })?;
+        seats.sort_unstable();
+        Ok(seats)
+    }
+
+    /// Get the maximum seat number of all.
+    fn part1(&self, seats: &Vec<u32>) -> Result<u32> {
+        seats.last().cloned().ok_or_else(|| "no seats".into())
+    }
+
+    /// Since we know that the missing seat -1 and +1 are in the list,
+    /// we can just check the range of seats and can ignore row
+    /// number. We do that by zipping with the complete range of seats
+    /// and pick the first element where the seat numbers do not match.
+    fn part2(&self, seats: &Vec<u32>) -> Result<u32> {
+        match (seats.first(), seats.last()) {
+            (Some(&min), Some(&max)) => (min..max)
+                .zip(seats)
+                .find(|(x, y)| x != *y)
+                .map(|(x, _)| x)
+                .ok_or_else(|| "no missing seat".into()),
+            _ => Err("no seats".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert_eq!(seat_id("FBFBBFFRLR"), 357);
+        assert_eq!(seat_id("BFFFBBFRRR"), 567);
+        assert_eq!(seat_id("FFFBBBFRRR"), 119);
+        assert_eq!(seat_id("BBFFBBFRLL"), 820);
+    }
+}
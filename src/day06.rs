@@ -0,0 +1,100 @@
+//! Day 6: Custom Customs
+
+use crate::{
+    error::{AocError, Result},
+    parse::paragraphs,
+    Solution,
+};
+use std::collections::HashSet;
+
+/// Sum over the groups of the number of questions anyone in the
+/// group answered yes to.
+pub fn part1<S: AsRef<str>>(forms: &[Vec<S>]) -> usize {
+    forms
+        .iter()
+        .map(|group| {
+            let result: HashSet<_> = group
+                .iter()
+                .map(|s| s.as_ref().chars().collect::<HashSet<_>>())
+                .reduce(|a, s| a.union(&s).cloned().collect())
+                .unwrap_or_default();
+            result.len()
+        })
+        .sum::<usize>()
+}
+
+/// Sum over the groups of the number of questions everyone in the
+/// group answered yes to.
+pub fn part2<S: AsRef<str>>(forms: &[Vec<S>]) -> usize {
+    forms
+        .iter()
+        .map(|group| {
+            let result: HashSet<_> = group
+                .iter()
+                .map(|s| s.as_ref().chars().collect::<HashSet<_>>())
+                .reduce(|a, s| a.intersection(&s).cloned().collect())
+                .unwrap_or_default();
+            result.len()
+        })
+        .sum::<usize>()
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<Vec<String>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Vec<Vec<String>>> {
+        paragraphs(text)
+            .iter()
+            .map(|group| {
+                group.parse_lines_with(|line| {
+                    match line
+                        .chars()
+                        .enumerate()
+                        .find(|(_, ch)| !ch.is_ascii_lowercase())
+                    {
+                        Some((col, ch)) => {
+                            Err(AocError::bad("unexpected character", &ch.to_string())
+                                .at_column(col + 1))
+                        }
+                        None => Ok(line.to_string()),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn part1(&self, forms: &Vec<Vec<String>>) -> Result<usize> {
+        Ok(part1(forms))
+    }
+
+    fn part2(&self, forms: &Vec<Vec<String>>) -> Result<usize> {
+        Ok(part2(forms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(part1(&[vec!["abc"]]), 3);
+        assert_eq!(part1(&[vec!["a", "b", "c"]]), 3);
+        assert_eq!(part1(&[vec!["ab", "ac"]]), 3);
+        assert_eq!(part1(&[vec!["a", "a", "a", "a"]]), 1);
+        assert_eq!(part1(&[vec!["b"]]), 1);
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(part2(&[vec!["abc"]]), 3);
+        assert_eq!(part2(&[vec!["a", "b", "c"]]), 0);
+        assert_eq!(part2(&[vec!["ab", "ac"]]), 1);
+        assert_eq!(part2(&[vec!["a", "a", "a", "a"]]), 1);
+        assert_eq!(part2(&[vec!["b"]]), 1);
+    }
+}
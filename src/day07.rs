@@ -0,0 +1,146 @@
+//! Day 7: Handy Haversacks
+
+use crate::{
+    error::{AocError, Result},
+    parse::parse_lines_with,
+    Solution,
+};
+use std::collections::HashMap;
+
+/// Adjective and color of a bag.
+pub type Bag = (String, String);
+/// The bags, with their counts, that each bag must contain.
+pub type Rules = HashMap<Bag, Vec<(usize, Bag)>>;
+
+fn parse(text: &str) -> Result<(usize, Bag)> {
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if words.len() < 3 || words.len() > 4 {
+        return Err(AocError::bad("bad bag description", text.trim()));
+    }
+    match words[0].parse() {
+        Ok(count) => Ok((count, (words[1].to_string(), words[2].to_string()))),
+        Err(_) => Ok((0, (words[0].to_string(), words[1].to_string()))),
+    }
+}
+
+fn parse_rule(line: &str) -> Result<(Bag, Vec<(usize, Bag)>)> {
+    let (key, tail) = line
+        .split_once("contain")
+        .ok_or_else(|| AocError::bad("rule without 'contain'", line.trim()).at_column(1))?;
+    let located = |desc: &str| parse(desc).map_err(|err| err.at_token(line, desc.trim()));
+    let bags = tail.split(',').map(located).collect::<Result<Vec<_>>>()?;
+    let rule = located(key)?.1;
+    Ok((rule, bags))
+}
+
+/// Read the rules, one per line.
+pub fn parse_rules(text: &str) -> Result<Rules> {
+    let mut map = HashMap::new();
+    for (lineno, (rule, bags)) in parse_lines_with(text, parse_rule)?.into_iter().enumerate() {
+        if map.insert(rule, bags).is_some() {
+            return Err(AocError::new("duplicate rule").at_line(lineno + 1));
+        }
+    }
+    Ok(map)
+}
+
+/// Whether a `start` bag eventually contains a `target` bag.
+pub fn can_contain(map: &Rules, start: &Bag, target: &Bag) -> bool {
+    match map.get(start) {
+        Some(bags) => bags
+            .iter()
+            .any(|(_, bag)| bag == target || can_contain(map, bag, target)),
+        None => false,
+    }
+}
+
+/// Number of bags inside a `start` bag.
+pub fn count_nodes(map: &Rules, start: &Bag) -> usize {
+    match map.get(start) {
+        Some(bags) => bags
+            .iter()
+            .map(|(cnt, bag)| cnt * (count_nodes(map, bag) + 1))
+            .sum(),
+        None => 0,
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Rules;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Rules> {
+        parse_rules(text)
+    }
+
+    fn part1(&self, map: &Rules) -> Result<usize> {
+        let target = ("shiny".to_string(), "gold".to_string());
+        let colors = map
+            .keys()
+            .filter(|bag| can_contain(map, bag, &target))
+            .count();
+        Ok(colors)
+    }
+
+    fn part2(&self, map: &Rules) -> Result<usize> {
+        let col = ("shiny".to_string(), "gold".to_string());
+        Ok(count_nodes(map, &col))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.
+"#;
+
+    #[test]
+    fn test_rule_parse() {
+        assert_eq!(
+            parse("1 bright white bag"),
+            Ok((1, ("bright".to_string(), "white".to_string())))
+        );
+        assert_eq!(
+            parse("2 muted yellow bags."),
+            Ok((2, ("muted".to_string(), "yellow".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_count() {
+        let examples = [
+            (("faded".to_string(), "blue".to_string()), 0),
+            (("dotted".to_string(), "black".to_string()), 0),
+            (("vibrant".to_string(), "plum".to_string()), 11),
+            (("dark".to_string(), "olive".to_string()), 7),
+            (("shiny".to_string(), "gold".to_string()), 32),
+        ];
+
+        let map = parse_rules(INPUT.trim()).unwrap();
+        for (col, expected) in &examples {
+            assert_eq!(count_nodes(&map, col), *expected, "color: {:?}", col);
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_rules("light red bags contain 1 bright white bag, 2 yellow.").unwrap_err();
+        assert_eq!(err.to_string(), "1:44: bad bag description '2 yellow.'");
+    }
+}
@@ -0,0 +1,296 @@
+//! Day 8: Handheld Halting
+
+use crate::{
+    error::{AocError, Result},
+    parse::parse_lines,
+    random::Rng,
+    Solution,
+};
+use std::{collections::HashSet, convert::TryFrom, str::FromStr};
+
+#[derive(Debug, Clone)]
+pub enum Instr {
+    Acc(i32),
+    Jmp(i32),
+    Nop(i32),
+}
+
+/// Machine running a program, with its program counter and
+/// accumulator.
+#[derive(Debug, Default)]
+pub struct Machine {
+    pub pc: i32,
+    pub acc: i32,
+}
+
+impl Instr {
+    /// Execute the instruction on the machine.
+    pub fn execute(&self, machine: &mut Machine) {
+        use Instr::*;
+
+        match self {
+            Acc(val) => {
+                machine.acc += val;
+                machine.pc += 1;
+            }
+            Jmp(val) => {
+                machine.pc += val;
+            }
+            Nop(_) => {
+                machine.pc += 1;
+            }
+        }
+    }
+}
+
+impl Machine {
+    /// Run a program until it terminates by moving just past its last
+    /// instruction, which gives `true`, or until an instruction is
+    /// about to run a second time or it jumps anywhere else outside
+    /// the program, which gives `false`.
+    pub fn run(&mut self, program: &[Instr]) -> bool {
+        let mut visited = HashSet::new();
+        loop {
+            let index = match usize::try_from(self.pc) {
+                Ok(index) if index == program.len() => return true,
+                Ok(index) if index < program.len() => index,
+                _ => return false,
+            };
+            if !visited.insert(index) {
+                return false;
+            }
+            program[index].execute(self);
+        }
+    }
+}
+
+/// Index of the instruction run after the one at `index`, if it is in
+/// the program or just after its end.
+fn next_index(program: &[Instr], index: usize, instr: &Instr) -> Option<usize> {
+    let offset = match instr {
+        Instr::Jmp(val) => *val as i64,
+        Instr::Acc(_) | Instr::Nop(_) => 1,
+    };
+    usize::try_from(index as i64 + offset)
+        .ok()
+        .filter(|&next| next <= program.len())
+}
+
+/// Whether the program terminates when started at each instruction,
+/// with an extra entry for the end of the program. Found by following
+/// the instructions backwards from the end.
+fn terminates_from(program: &[Instr]) -> Vec<bool> {
+    let mut sources = vec![vec![]; program.len() + 1];
+    for (index, instr) in program.iter().enumerate() {
+        if let Some(next) = next_index(program, index, instr) {
+            sources[next].push(index);
+        }
+    }
+    let mut terminates = vec![false; program.len() + 1];
+    terminates[program.len()] = true;
+    let mut todo = vec![program.len()];
+    while let Some(index) = todo.pop() {
+        for &source in &sources[index] {
+            if !terminates[source] {
+                terminates[source] = true;
+                todo.push(source);
+            }
+        }
+    }
+    terminates
+}
+
+/// Repair a program that loops by changing one `jmp` to `nop` or one
+/// `nop` to `jmp`, so that it terminates. Only instructions that are
+/// run before the program loops can make a difference, and changing
+/// one of them works if the program terminates when started after it.
+/// Of those that work, the first in the program is changed.
+pub fn repair(program: &[Instr]) -> Option<Vec<Instr>> {
+    let terminates = terminates_from(program);
+    let mut visited = vec![false; program.len()];
+    let mut repairs = vec![];
+    let mut index = 0;
+    while index < program.len() && !visited[index] {
+        visited[index] = true;
+        let patched = match program[index] {
+            Instr::Jmp(val) => Some(Instr::Nop(val)),
+            Instr::Nop(val) => Some(Instr::Jmp(val)),
+            Instr::Acc(_) => None,
+        };
+        if let Some(patched) = patched {
+            if next_index(program, index, &patched).is_some_and(|next| terminates[next]) {
+                repairs.push((index, patched));
+            }
+        }
+        index = next_index(program, index, &program[index])?;
+    }
+    let (index, patched) = repairs.into_iter().min_by_key(|&(index, _)| index)?;
+    let mut program = program.to_vec();
+    program[index] = patched;
+    Some(program)
+}
+
+/// Random program with `size` instructions, where every `jmp` and
+/// `nop` would jump into the program or just after its end.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<Instr> {
+    (0..size)
+        .map(|index| {
+            let offset = rng.range(-(index as i64), (size - index) as i64) as i32;
+            match rng.below(3) {
+                0 => Instr::Acc(rng.range(-50, 50) as i32),
+                1 => Instr::Jmp(offset),
+                _ => Instr::Nop(offset),
+            }
+        })
+        .collect()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<Instr>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(&self, text: &str) -> Result<Vec<Instr>> {
+        parse_lines(text)
+    }
+
+    /// Run the machine until it loops.
+    fn part1(&self, program: &Vec<Instr>) -> Result<i32> {
+        let mut machine = Machine { pc: 0, acc: 0 };
+        machine.run(program);
+        Ok(machine.acc)
+    }
+
+    /// Change the instruction that makes the program loop and run the
+    /// repaired program.
+    fn part2(&self, program: &Vec<Instr>) -> Result<i32> {
+        let program = repair(program).ok_or("no patched program terminates")?;
+        let mut machine = Machine::default();
+        machine.run(&program);
+        Ok(machine.acc)
+    }
+}
+
+impl FromStr for Instr {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<Instr> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        if words.len() != 2 {
+            return Err(AocError::bad("bad instruction", text.trim()).at_column(1));
+        }
+        let operand = words[1]
+            .parse()
+            .map_err(|_| AocError::bad("bad operand", words[1]).at_token(text, words[1]))?;
+        match words[0] {
+            "acc" => Ok(Instr::Acc(operand)),
+            "jmp" => Ok(Instr::Jmp(operand)),
+            "nop" => Ok(Instr::Nop(operand)),
+            op => Err(AocError::bad("unknown instruction", op).at_token(text, op)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{cross_check, Shrink};
+
+    impl Shrink for Instr {
+        fn shrink(&self) -> Vec<Instr> {
+            match *self {
+                Instr::Acc(val) => val.shrink().into_iter().map(Instr::Acc).collect(),
+                Instr::Jmp(val) => Some(Instr::Nop(val))
+                    .into_iter()
+                    .chain(val.shrink().into_iter().map(Instr::Jmp))
+                    .collect(),
+                Instr::Nop(val) => val.shrink().into_iter().map(Instr::Nop).collect(),
+            }
+        }
+    }
+
+    /// Whether every `jmp` and `nop` would jump into the program or
+    /// just after its end, and the program loops, as in the puzzle.
+    fn is_puzzle(program: &[Instr]) -> bool {
+        let in_range = program
+            .iter()
+            .enumerate()
+            .all(|(index, instr)| match instr {
+                Instr::Jmp(val) | Instr::Nop(val) => {
+                    next_index(program, index, &Instr::Jmp(*val)).is_some()
+                }
+                Instr::Acc(_) => true,
+            });
+        in_range && !Machine::default().run(program)
+    }
+
+    /// Change each instruction in turn and run the program to see if it
+    /// terminates.
+    fn part2_naive(program: &[Instr]) -> Option<i32> {
+        for i in 0..program.len() {
+            let instr = match program[i] {
+                Instr::Nop(v) => Instr::Jmp(v),
+                Instr::Jmp(v) => Instr::Nop(v),
+                _ => continue,
+            };
+            let mut program = program.to_vec();
+            program[i] = instr;
+            let mut machine = Machine { pc: 0, acc: 0 };
+            if machine.run(&program) {
+                return Some(machine.acc);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_example() {
+        let program = Day08
+            .parse("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n")
+            .unwrap();
+        assert_eq!(Day08.part1(&program), Ok(5));
+        assert_eq!(Day08.part2(&program), Ok(8));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let run = |program: Option<Vec<Instr>>| {
+            let mut machine = Machine::default();
+            program.filter(|program| machine.run(program))?;
+            Some(machine.acc)
+        };
+        cross_check(
+            "day 8 part 2",
+            2000,
+            generate,
+            |program| {
+                Some(program)
+                    .filter(|p| is_puzzle(p))
+                    .and_then(|p| part2_naive(p))
+            },
+            |program| {
+                Some(program)
+                    .filter(|p| is_puzzle(p))
+                    .and_then(|p| run(repair(p)))
+            },
+        );
+    }
+
+    #[test]
+    fn test_run_bounds() {
+        let run = |text: &str| Machine::default().run(&Day08.parse(text).unwrap());
+        assert!(run("acc +1\njmp +1\n"));
+        assert!(!run("nop +0\njmp -2\n"));
+        assert!(!run("nop +0\njmp +2\n"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day08.parse("nop +0\nacc +x1\n").unwrap_err();
+        assert_eq!(err.to_string(), "2:5: bad operand '+x1'");
+        let err = Day08.parse("nop +0\nmul +1\n").unwrap_err();
+        assert_eq!(err.to_string(), "2:1: unknown instruction 'mul'");
+    }
+}
@@ -0,0 +1,112 @@
+//! Day 9: Encoding Error
+
+use crate::{error::Result, parse::parse_lines, random::Rng, Solution};
+use std::iter::repeat;
+
+/// Size of the preamble and of the window of numbers considered.
+pub const WINDOW: usize = 25;
+
+/// Whether `n` is not the sum of two of the numbers in `nums`.
+pub fn is_invalid(n: usize, nums: &[usize]) -> bool {
+    nums.iter()
+        .enumerate()
+        .flat_map(|(i, x)| repeat(x).zip(&nums[(i + 1)..]))
+        .filter(|(&x, &y)| x + y == n)
+        .all(|_| false)
+}
+
+/// First number that is not the sum of two of the `w` numbers
+/// before it.
+pub fn part1(nums: &[usize], w: usize) -> Option<usize> {
+    (w..nums.len())
+        .find(|&i| is_invalid(nums[i], &nums[(i - w)..i]))
+        .map(|i| nums[i])
+}
+
+/// Sum of the smallest and the largest number in a contiguous range
+/// of at least two numbers that sums to `number`. Of the ranges that
+/// do, the one that ends first is used, and the longest of those.
+pub fn part2(numbers: &[usize], number: usize) -> Option<usize> {
+    let mut start = 0;
+    let mut sum = 0;
+    for (end, &n) in numbers.iter().enumerate() {
+        sum += n;
+        while sum > number {
+            sum -= numbers[start];
+            start += 1;
+        }
+        if sum == number && start < end {
+            let range = &numbers[start..=end];
+            return Some(range.iter().max().unwrap() + range.iter().min().unwrap());
+        }
+    }
+    None
+}
+
+/// Random list of `size` small numbers, so that many ranges have the
+/// same sum.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<usize> {
+    (0..size).map(|_| rng.below(20) as usize).collect()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Vec<usize>> {
+        parse_lines(text)
+    }
+
+    fn part1(&self, numbers: &Vec<usize>) -> Result<usize> {
+        part1(numbers, WINDOW).ok_or_else(|| "no invalid number".into())
+    }
+
+    fn part2(&self, numbers: &Vec<usize>) -> Result<usize> {
+        let number = self.part1(numbers)?;
+        part2(numbers, number).ok_or_else(|| "no contiguous range".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::cross_check;
+
+    /// Try all ranges, in the order of `part2`.
+    fn part2_naive(numbers: &[usize], number: usize) -> Option<usize> {
+        (0..numbers.len())
+            .flat_map(|end| (0..end).map(move |start| &numbers[start..=end]))
+            .find(|range| range.iter().sum::<usize>() == number)
+            .map(|range| range.iter().max().unwrap() + range.iter().min().unwrap())
+    }
+
+    #[test]
+    fn test_example() {
+        let numbers = [
+            35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309,
+            576,
+        ];
+        assert_eq!(part1(&numbers, 5), Some(127));
+        assert_eq!(part2(&numbers, 127), Some(62));
+        // The range can end with the last number.
+        assert_eq!(part2(&[1, 2, 3], 5), Some(5));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        cross_check(
+            "day 9 part 2",
+            2000,
+            |rng, size| {
+                let numbers = generate(rng, size);
+                let number = rng.below(60) as usize;
+                (numbers, number)
+            },
+            |(numbers, number)| part2_naive(numbers, *number),
+            |(numbers, number)| part2(numbers, *number),
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Day 10: Adapter Array
+
+use crate::{error::Result, parse::parse_lines, Solution};
+
+/// Number of 1-jolt differences times the number of 3-jolt
+/// differences when chaining all adapters.
+pub fn part1(mut jolts: Vec<usize>) -> usize {
+    jolts.sort_unstable();
+    let mut prev = 0;
+    let mut dist = [0, 0, 0, 1];
+    for j in jolts {
+        dist[j - prev] += 1;
+        prev = j;
+    }
+    dist[1] * dist[3]
+}
+
+/// Number of distinct arrangements of adapters that connect the
+/// outlet to the device.
+pub fn part2(jolts: Vec<usize>) -> usize {
+    Memo::new(jolts).arrs(0, 0)
+}
+
+/// Memoizer for the sub-solutions.
+pub struct Memo {
+    adapters: Vec<usize>,
+    done: Vec<Option<usize>>,
+}
+
+impl Memo {
+    /// Memoizer for the adapters, with the device added last.
+    pub fn new(mut adapters: Vec<usize>) -> Memo {
+        let mut done = vec![];
+        adapters.sort_unstable();
+        adapters.push(adapters.last().unwrap() + 3);
+        done.resize(adapters.len(), None);
+        Memo { adapters, done }
+    }
+
+    fn remember(&mut self, idx: usize, val: usize) -> usize {
+        self.done[idx] = Some(val);
+        val
+    }
+
+    /// Number of arrangements of the adapters from index `idx` on,
+    /// when the joltage of the previous adapter is `curr`.
+    pub fn arrs(&mut self, curr: usize, idx: usize) -> usize {
+        if idx < self.adapters.len() - 1 {
+            if let Some(value) = self.done[idx] {
+                return value;
+            }
+
+            let candidates: Vec<_> = (idx..self.adapters.len())
+                .take_while(|&i| self.adapters[i] - curr <= 3)
+                .collect();
+
+            let result = candidates
+                .into_iter()
+                .map(|i| self.arrs(self.adapters[i], i + 1))
+                .sum();
+            self.remember(idx, result)
+        } else {
+            self.remember(idx, 1)
+        }
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Vec<usize>> {
+        let jolts = parse_lines(text)?;
+        if jolts.is_empty() {
+            return Err("no adapters".into());
+        }
+        Ok(jolts)
+    }
+
+    fn part1(&self, jolts: &Vec<usize>) -> Result<usize> {
+        Ok(part1(jolts.clone()))
+    }
+
+    fn part2(&self, jolts: &Vec<usize>) -> Result<usize> {
+        Ok(part2(jolts.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aoc10_part2() {
+        let jolts = vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4];
+        assert_eq!(part2(jolts), 8);
+        let jolts = vec![
+            28, 33, 18, 42, 31, 14, 46, 20, 48, 47, 24, 23, 49, 45, 19, 38, 39, 11, 1, 32, 25, 35,
+            8, 17, 7, 9, 4, 2, 34, 10, 3,
+        ];
+        assert_eq!(part2(jolts), 19208);
+    }
+}
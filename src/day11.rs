@@ -0,0 +1,177 @@
+//! Day 11: Seating System
+
+use crate::{
+    debug,
+    error::{AocError, Result},
+    trace, Solution,
+};
+
+/// Seat layout, with `.` for floor, `L` for empty seats, and `#`
+/// for occupied seats.
+pub type Board = Vec<Vec<u8>>;
+
+fn dec(x: usize) -> usize {
+    if x == 0 {
+        0
+    } else {
+        x - 1
+    }
+}
+
+/// The board as text, one row per line.
+fn show(board: &[Vec<u8>]) -> String {
+    board
+        .iter()
+        .map(|row| String::from_utf8_lossy(row))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Apply the seating rules until nothing changes and return the
+/// number of occupied seats. An occupied seat is emptied if `check`
+/// counts at least `limit` occupied seats, and an empty seat is
+/// occupied if `check` counts none.
+pub fn run<F>(mut board: Board, limit: usize, check: F) -> usize
+where
+    F: Fn(&[Vec<u8>], usize, usize) -> usize,
+{
+    let rows = board.len();
+    let cols = board[0].len();
+
+    loop {
+        let mut changes = vec![];
+        let mut total_occupied = 0;
+        for row in 0..rows {
+            for col in 0..cols {
+                match board[row][col] {
+                    b'L' if check(&board, row, col) == 0 => changes.push((b'#', row, col)),
+                    b'#' if check(&board, row, col) >= limit => {
+                        total_occupied += 1;
+                        changes.push((b'L', row, col))
+                    }
+                    b'#' => total_occupied += 1,
+
+                    _ => (),
+                }
+            }
+        }
+
+        if changes.is_empty() {
+            return total_occupied;
+        }
+        for change in changes {
+            board[change.1][change.2] = change.0;
+        }
+        debug!("board:\n{}", show(&board));
+    }
+}
+
+fn occupied(board: &[Vec<u8>], row: usize, col: usize, dir: (isize, isize)) -> bool {
+    let mut row: isize = row as isize + dir.0;
+    let mut col: isize = col as isize + dir.1;
+    while 0 <= row
+        && 0 <= col
+        && (row as usize) < board.len()
+        && (col as usize) < board[row as usize].len()
+    {
+        let ch = board[row as usize][col as usize];
+        trace!("row: {}, col: {}, char: {}", row, col, ch as char);
+        match ch {
+            b'#' => return true,
+            b'L' => return false,
+            _ => (),
+        }
+        row += dir.0;
+        col += dir.1;
+    }
+    false
+}
+
+/// Number of occupied seats visible in the eight directions.
+pub fn visible_occupied(board: &[Vec<u8>], row: usize, col: usize) -> usize {
+    let dirs = vec![
+        (-1, -1),
+        (-1, 0),
+        (-1, 1),
+        (0, -1),
+        (0, 1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+    ];
+
+    assert!(board[row][col] != b'.');
+
+    let mut result = 0;
+    for dir in dirs {
+        if occupied(board, row, col, dir) {
+            trace!("occupied - row: {}, col: {}, dir: {:?}", row, col, dir);
+            result += 1;
+        } else {
+            trace!("not occupied - row: {}, col: {}, dir: {:?}", row, col, dir);
+        }
+    }
+    result
+}
+
+/// Number of occupied seats adjacent to a seat.
+pub fn nearby_occupied(board: &[Vec<u8>], row: usize, col: usize) -> usize {
+    let mut result = 0;
+    for i in dec(row)..(row + 2) {
+        for j in dec(col)..(col + 2) {
+            if i == row && j == col {
+                continue;
+            }
+            if i < board.len() && j < board[i].len() && board[i][j] == b'#' {
+                result += 1;
+            }
+        }
+    }
+    result
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Board;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Board> {
+        let mut board: Board = vec![];
+        for (lineno, line) in text.lines().enumerate() {
+            if let Some((col, ch)) = line
+                .chars()
+                .enumerate()
+                .find(|(_, ch)| !".L#".contains(*ch))
+            {
+                return Err(AocError::bad("unexpected character", &ch.to_string())
+                    .at_column(col + 1)
+                    .at_line(lineno + 1));
+            }
+            if let Some(first) = board.first() {
+                if first.len() != line.len() {
+                    return Err(AocError::new(format!(
+                        "row has length {}, expected {}",
+                        line.len(),
+                        first.len()
+                    ))
+                    .at_line(lineno + 1));
+                }
+            }
+            board.push(line.as_bytes().to_vec());
+        }
+        if board.is_empty() {
+            return Err(AocError::new("empty board"));
+        }
+        Ok(board)
+    }
+
+    fn part1(&self, board: &Board) -> Result<usize> {
+        Ok(run(board.clone(), 4, nearby_occupied))
+    }
+
+    fn part2(&self, board: &Board) -> Result<usize> {
+        Ok(run(board.clone(), 5, visible_occupied))
+    }
+}
@@ -0,0 +1,152 @@
+//! Day 12: Rain Risk
+
+use crate::{
+    error::{AocError, Result},
+    parse::parse_lines,
+    Solution,
+};
+use num::{complex::Complex, pow};
+use std::str::FromStr;
+
+const ROTATE_RIGHT: Complex<isize> = Complex::new(0, 1);
+const ROTATE_LEFT: Complex<isize> = Complex::new(0, -1);
+const MOVE_WEST: Complex<isize> = Complex::new(-1, 0);
+const MOVE_EAST: Complex<isize> = Complex::new(1, 0);
+const MOVE_SOUTH: Complex<isize> = Complex::new(0, 1);
+const MOVE_NORTH: Complex<isize> = Complex::new(0, -1);
+
+/// Position of the ship and its waypoint, with east and south as the
+/// positive directions.
+#[derive(Debug)]
+pub struct State {
+    pub pos: Complex<isize>,
+    pub waypoint: Complex<isize>,
+}
+
+impl State {
+    pub fn new(pos: Complex<isize>, waypoint: Complex<isize>) -> State {
+        State { pos, waypoint }
+    }
+
+    /// Apply the actions in order using `transform`.
+    pub fn run<F>(&mut self, actions: &[Action], transform: F)
+    where
+        F: Fn(&mut State, &Action),
+    {
+        actions.iter().for_each(|action| transform(self, action))
+    }
+}
+
+/// Move as in part 1, where the waypoint is the direction of the
+/// ship.
+pub fn transform1(state: &mut State, action: &Action) {
+    match action {
+        Action::North(n) => state.pos += n * MOVE_NORTH,
+        Action::South(n) => state.pos += n * MOVE_SOUTH,
+        Action::East(n) => state.pos += n * MOVE_EAST,
+        Action::West(n) => state.pos += n * MOVE_WEST,
+        Action::Right(d) => state.waypoint *= pow(ROTATE_RIGHT, (d / 90) as usize),
+        Action::Left(d) => state.waypoint *= pow(ROTATE_LEFT, (d / 90) as usize),
+        Action::Forward(n) => state.pos += n * state.waypoint,
+    }
+}
+
+/// Move as in part 2, where the actions move the waypoint.
+pub fn transform2(state: &mut State, action: &Action) {
+    match action {
+        Action::North(n) => state.waypoint += n * MOVE_NORTH,
+        Action::South(n) => state.waypoint += n * MOVE_SOUTH,
+        Action::East(n) => state.waypoint += n * MOVE_EAST,
+        Action::West(n) => state.waypoint += n * MOVE_WEST,
+        Action::Right(d) => state.waypoint *= pow(ROTATE_RIGHT, (d / 90) as usize),
+        Action::Left(d) => state.waypoint *= pow(ROTATE_LEFT, (d / 90) as usize),
+        Action::Forward(n) => state.pos += n * state.waypoint,
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Action {
+    North(isize),
+    South(isize),
+    East(isize),
+    West(isize),
+    Left(isize),
+    Right(isize),
+    Forward(isize),
+}
+
+impl FromStr for Action {
+    type Err = AocError;
+    fn from_str(input: &str) -> Result<Action> {
+        let mut chars = input.chars();
+        let action = chars
+            .next()
+            .ok_or_else(|| AocError::new("missing action"))?;
+        let value = chars.as_str();
+        let n: isize = value
+            .parse()
+            .map_err(|_| AocError::bad("invalid number", value).at_column(2))?;
+        match action {
+            'N' => Ok(Action::North(n)),
+            'S' => Ok(Action::South(n)),
+            'E' => Ok(Action::East(n)),
+            'W' => Ok(Action::West(n)),
+            'F' => Ok(Action::Forward(n)),
+            'L' => Ok(Action::Left(n)),
+            'R' => Ok(Action::Right(n)),
+            _ => Err(AocError::bad("unknown action", &action.to_string()).at_column(1)),
+        }
+    }
+}
+
+/// Manhattan distance from the origin.
+pub fn distance(pos: Complex<isize>) -> isize {
+    pos.re.abs() + pos.im.abs()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Action>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(&self, text: &str) -> Result<Vec<Action>> {
+        parse_lines(text)
+    }
+
+    fn part1(&self, actions: &Vec<Action>) -> Result<isize> {
+        let mut state = State::new(Complex::new(0, 0), Complex::new(1, 0));
+        state.run(actions, transform1);
+        Ok(distance(state.pos))
+    }
+
+    fn part2(&self, actions: &Vec<Action>) -> Result<isize> {
+        let mut state = State::new(Complex::new(0, 0), Complex::new(10, -1));
+        state.run(actions, transform2);
+        Ok(distance(state.pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_parse() {
+        assert_eq!("N10".parse::<Action>(), Ok(Action::North(10)));
+        assert_eq!("S10".parse::<Action>(), Ok(Action::South(10)));
+        assert_eq!("E10".parse::<Action>(), Ok(Action::East(10)));
+        assert_eq!("W10".parse::<Action>(), Ok(Action::West(10)));
+        assert_eq!("L10".parse::<Action>(), Ok(Action::Left(10)));
+        assert_eq!("R10".parse::<Action>(), Ok(Action::Right(10)));
+        assert_eq!("F10".parse::<Action>(), Ok(Action::Forward(10)));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day12.parse("F10\nX3\n").unwrap_err();
+        assert_eq!(err.to_string(), "2:1: unknown action 'X'");
+        let err = Day12.parse("F10\nN3x\n").unwrap_err();
+        assert_eq!(err.to_string(), "2:2: invalid number '3x'");
+    }
+}
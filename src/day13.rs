@@ -0,0 +1,133 @@
+//! Day 13: Shuttle Search
+
+use crate::{
+    error::{AocError, Result},
+    parse::parse_token,
+    Solution,
+};
+
+/// Earliest timestamp and the bus lines, where `None` is a line that
+/// is out of service.
+pub struct Notes {
+    timestamp: isize,
+    lines: Vec<Option<isize>>,
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Notes;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(&self, text: &str) -> Result<Notes> {
+        let cvec: Vec<&str> = text.split('\n').collect();
+        if cvec.len() < 2 {
+            return Err(AocError::new("expected timestamp and bus lines"));
+        }
+        let timestamp = parse_token(cvec[0], cvec[0].trim()).map_err(|err| err.at_line(1))?;
+        let lines = cvec[1]
+            .split(',')
+            .map(|s| match s.trim() {
+                "x" => Ok(None),
+                id => parse_token(cvec[1], id).map(Some),
+            })
+            .collect::<Result<_>>()
+            .map_err(|err| err.at_line(2))?;
+        Ok(Notes { timestamp, lines })
+    }
+
+    fn part1(&self, notes: &Notes) -> Result<isize> {
+        let timestamp = notes.timestamp;
+        let (line, departure) = notes
+            .lines
+            .iter()
+            .flatten()
+            .map(|&x| (x, x - timestamp % x))
+            .min_by(|x, y| x.1.cmp(&y.1))
+            .ok_or("no bus lines in service")?;
+        Ok(line * departure)
+    }
+
+    fn part2(&self, notes: &Notes) -> Result<isize> {
+        let solution = notes
+            .lines
+            .iter()
+            .enumerate()
+            .filter_map(|(e, s)| s.map(|x| (e as isize, x)))
+            .collect::<Vec<(isize, isize)>>();
+        match solve(&solution) {
+            Some((mut a, m)) => {
+                if a < 0 {
+                    a = -a % m;
+                }
+                Ok(a)
+            }
+            None => Err("bus lines are not coprime".into()),
+        }
+    }
+}
+
+/// Chinese Reminder Theorem solver. Using iterated version of
+/// extended euclidian (and 64 bit numbers, otherwise this would be a
+/// pain).
+pub fn solve(v: &[(isize, isize)]) -> Option<(isize, isize)> {
+    let n: isize = v.iter().map(|e| e.1).product();
+    let s: Option<isize> = v
+        .iter()
+        .map(|(a, m)| invert(n / m, *m).map(|i| a * (n / m) * i))
+        .sum();
+    s.map(|v| (v, n))
+}
+
+/// Extended Euclidian.
+///
+/// Compute x and y and g = gcd(a,b) such that ax + by = g. The values
+/// x and y are called the *Bezout coefficients.*
+pub fn euclidean(a: isize, b: isize) -> (isize, isize, isize) {
+    let mut x = (a, b);
+    let mut y = (1, 0);
+    let mut z = (0, 1);
+    while x.1 > 0 {
+        let q = x.0 / x.1;
+        let new_x = (x.1, x.0 - q * x.1);
+        x = new_x;
+        let new_y = (y.1, y.0 - q * y.1);
+        y = new_y;
+        let new_z = (z.1, z.0 - q * z.1);
+        z = new_z;
+    }
+    (x.0, y.0, z.0)
+}
+
+/// Inverse of `a` modulo `m`, if they are coprime.
+pub fn invert(a: isize, m: isize) -> Option<isize> {
+    let (g, x, _) = euclidean(a, m);
+    if g == 1 {
+        Some(x % m)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_euclidean() {
+        assert_eq!(euclidean(240, 46), (2, -9, 47));
+    }
+
+    #[test]
+    fn test_invert() {
+        assert_eq!(invert(17, 43), Some(-5));
+        assert_eq!(invert(240, 46), None);
+    }
+
+    #[test]
+    fn test_solve() {
+        let vs = [(0, 7), (1, 13), (4, 59), (6, 31), (7, 19)];
+        assert_eq!(solve(&vs), Some((-4231122, 3162341)));
+    }
+}
@@ -0,0 +1,175 @@
+//! Day 14: Docking Data
+
+use crate::{
+    error::{AocError, Result},
+    parse::parse_lines_with,
+    Solution,
+};
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Machine executing the initialization program, where `gen`
+/// generates the masks applied when writing to memory.
+pub struct Mach<G>
+where
+    G: Fn(Vec<(usize, usize)>, char) -> Vec<(usize, usize)>,
+{
+    mem: HashMap<usize, usize>,
+    masks: Vec<(usize, usize)>,
+    mask_re: Regex,
+    mem_re: Regex,
+    gen: G,
+}
+
+impl<G> Mach<G>
+where
+    G: Fn(Vec<(usize, usize)>, char) -> Vec<(usize, usize)>,
+{
+    pub fn new(gen: G) -> Mach<G> {
+        Mach {
+            mem: HashMap::new(),
+            masks: Vec::new(),
+            mask_re: Regex::new(r"mask\s*=\s*([01X]+)").unwrap(),
+            mem_re: Regex::new(r"mem\[(\d+)\]\s*=\s*(\d+)").unwrap(),
+            gen,
+        }
+    }
+
+    /// Execute a mask or memory assignment line.
+    pub fn execute(&mut self, line: &str) {
+        if let Some(caps) = self.mask_re.captures(line) {
+            self.masks = caps
+                .get(1)
+                .unwrap()
+                .as_str()
+                .chars()
+                .fold(vec![(0, !0)], &self.gen);
+        } else if let Some(caps) = self.mem_re.captures(line) {
+            let mem: usize = caps.get(1).unwrap().as_str().parse().unwrap();
+            let val: usize = caps.get(2).unwrap().as_str().parse().unwrap();
+            for mask in &self.masks {
+                let mem = (mem & mask.1) | mask.0;
+                self.mem.insert(mem, val);
+            }
+        }
+    }
+
+    /// Sum of the values in memory.
+    pub fn memory_sum(&self) -> usize {
+        self.mem.values().sum()
+    }
+}
+
+/// Masks for part 1, where the mask is applied to the value.
+pub fn gen_masks_1(masks: Vec<(usize, usize)>, c: char) -> Vec<(usize, usize)> {
+    masks
+        .iter()
+        .map(|(x, y)| match c {
+            '0' => (x << 1, y << 1),
+            '1' => (x << 1 | 1, y << 1 | 1),
+            'X' => (x << 1, y << 1 | 1),
+            _ => todo!(),
+        })
+        .collect()
+}
+
+/// Masks for part 2, where the mask is applied to the address and
+/// floating bits give all combinations.
+pub fn gen_masks_2(masks: Vec<(usize, usize)>, c: char) -> Vec<(usize, usize)> {
+    match c {
+        '0' => masks.iter().map(|(x, y)| (x << 1, y << 1 | 1)).collect(),
+        '1' => masks
+            .iter()
+            .map(|(x, y)| (x << 1 | 1, y << 1 | 1))
+            .collect(),
+        'X' => masks
+            .iter()
+            .flat_map(|(x, y)| vec![(x << 1 | 1, y << 1), (x << 1, y << 1)])
+            .collect(),
+        _ => todo!(),
+    }
+}
+
+/// Check that a line is either a mask or a memory assignment that
+/// [`Mach::execute`] understands.
+fn check_line(line: &str) -> Result<()> {
+    let line_re = Regex::new(r"^(?:mask\s*=\s*([01X]{36})|mem\[(\d+)\]\s*=\s*(\d+))\s*$").unwrap();
+    let caps = line_re
+        .captures(line)
+        .ok_or_else(|| AocError::bad("invalid instruction", line.trim()).at_column(1))?;
+    for number in caps.iter().skip(2).flatten() {
+        number.as_str().parse::<usize>().map_err(|_| {
+            AocError::bad("invalid number", number.as_str()).at_column(number.start() + 1)
+        })?;
+    }
+    Ok(())
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Vec<String>> {
+        parse_lines_with(text, |line| {
+            check_line(line)?;
+            Ok(line.to_string())
+        })
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> Result<usize> {
+        let mut mach = Mach::new(gen_masks_1);
+        for line in lines {
+            mach.execute(line);
+        }
+        Ok(mach.memory_sum())
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> Result<usize> {
+        let mut mach = Mach::new(gen_masks_2);
+        for line in lines {
+            mach.execute(line);
+        }
+        Ok(mach.memory_sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let prog = [
+            "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X",
+            "mem[8] = 11",
+            "mem[7] = 101",
+            "mem[8] = 0",
+        ];
+        let mut mach = Mach::new(gen_masks_1);
+        for line in &prog {
+            mach.execute(line);
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day14.parse("mem[8] = 11\nmem[x] = 3\n").unwrap_err();
+        assert_eq!(err.to_string(), "2:1: invalid instruction 'mem[x] = 3'");
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(
+            "000000000000000000000000000000X1001X"
+                .chars()
+                .fold(vec![(0, !0)], gen_masks_2)
+                .iter()
+                .map(|m| (42 & m.1) | m.0)
+                .collect::<Vec<_>>(),
+            vec![59, 58, 27, 26]
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! Day 15: Rambunctious Recitation
+
+use crate::{debug, error::Result, parse::comma_separated, Solution};
+use std::collections::HashMap;
+
+/// Number spoken on turn `nth` of the game starting with `numbers`.
+pub fn play(numbers: &[usize], nth: usize) -> usize {
+    let mut record: HashMap<_, _> = numbers.iter().enumerate().map(|(x, &y)| (y, x)).collect();
+    let mut spoken = numbers[numbers.len() - 1];
+    for turn in (numbers.len() - 1)..(nth - 1) {
+        if turn % 100000 == 0 {
+            debug!("turn {}", turn);
+        }
+        spoken = match record.insert(spoken, turn) {
+            None => 0,
+            Some(last) => turn - last,
+        };
+    }
+    spoken
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Vec<usize>> {
+        comma_separated(text.trim_end()).map_err(|err| err.at_line(1))
+    }
+
+    fn part1(&self, numbers: &Vec<usize>) -> Result<usize> {
+        Ok(play(numbers, 2020))
+    }
+
+    fn part2(&self, numbers: &Vec<usize>) -> Result<usize> {
+        Ok(play(numbers, 30000000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_eq!(play(&[0, 3, 6], 2020), 436);
+        assert_eq!(play(&[1, 3, 2], 2020), 1);
+        assert_eq!(play(&[2, 1, 3], 2020), 10);
+    }
+}
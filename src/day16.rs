@@ -0,0 +1,220 @@
+//! Day 16: Ticket Translation
+
+use crate::{
+    debug,
+    error::{AocError, Result},
+    parse::{comma_separated, paragraphs, parse_token, Paragraph},
+    trace, Solution,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+/// The rules, by field name.
+pub type Rules = HashMap<String, Rule>;
+
+/// The notes on the tickets: the rules for the fields, your ticket,
+/// and the nearby tickets.
+#[derive(Debug)]
+pub struct Notes {
+    rules: Rules,
+    your: Vec<usize>,
+    nearby: Vec<Vec<usize>>,
+}
+
+/// Parse the tickets in a section of the notes, skipping the heading.
+fn parse_tickets(section: &Paragraph) -> Result<Vec<Vec<usize>>> {
+    section.body().parse_lines_with(comma_separated)
+}
+
+/// Ticket scanning error rate: the sum of the values on the tickets
+/// that are not valid for any field.
+pub fn part1(rules: &Rules, tickets: &[Vec<usize>]) -> usize {
+    let mut found = Vec::new();
+    'ticket: for ticket in tickets {
+        let mut invalid: HashSet<usize> = ticket.iter().cloned().collect();
+        for rule in rules {
+            let found = get_invalid(rule.1, ticket).iter().cloned().collect();
+            invalid = invalid.intersection(&found).cloned().collect();
+            if invalid.is_empty() {
+                continue 'ticket;
+            }
+        }
+        let mut ivec: Vec<usize> = invalid.iter().cloned().collect();
+        found.append(&mut ivec);
+    }
+    found.iter().sum::<usize>()
+}
+
+/// Product of the fields of your ticket whose names start with
+/// "departure".
+pub fn part2(your: &[usize], rules: &Rules, nearby: &[Vec<usize>]) -> Option<usize> {
+    let mut tickets: Vec<Vec<usize>> = nearby
+        .iter()
+        .filter(|ticket| is_valid_ticket(rules, ticket))
+        .cloned()
+        .collect();
+    tickets.push(your.to_vec());
+    let matching = compute_matching(rules, &tickets);
+    let mut order: Vec<usize> = (0..matching.len()).collect();
+    order.sort_by(|x, y| matching[*x].len().cmp(&matching[*y].len()));
+    debug!("order: {:?}", order);
+    let fields = recurse(vec![], &order, &matching)?;
+    debug!("fields: {:?}", fields);
+    let answer: usize = order
+        .iter()
+        .zip(fields)
+        .filter(|(_, n)| n.starts_with("departure"))
+        .map(|(i, _)| your[*i])
+        .product();
+    Some(answer)
+}
+
+fn compute_matching<'a>(rules: &'a Rules, tickets: &[Vec<usize>]) -> Vec<Vec<&'a Rule>> {
+    let mut matching = Vec::new();
+    for index in 0..tickets[0].len() {
+        let heads: Vec<usize> = tickets.iter().map(|ticket| ticket[index]).collect();
+        let matches: Vec<_> = rules
+            .values()
+            .filter(|rule| heads.iter().all(|&head| rule.good(head)))
+            .collect();
+        matching.push(matches);
+    }
+    matching
+}
+
+fn recurse(path: Vec<String>, order: &[usize], matching: &[Vec<&Rule>]) -> Option<Vec<String>> {
+    if !order.is_empty() {
+        trace!("path: {:?}, index: {}", path, order[0]);
+        for rule in matching[order[0]]
+            .iter()
+            .filter(|rule| path.iter().all(|s| *s != rule.name))
+        {
+            let mut path = path.clone();
+            path.push(rule.name.clone());
+            if let Some(path) = recurse(path, &order[1..], matching) {
+                return Some(path);
+            }
+        }
+        None
+    } else {
+        Some(path)
+    }
+}
+
+fn is_valid_ticket(rules: &Rules, ticket: &[usize]) -> bool {
+    ticket
+        .iter()
+        .all(|val| rules.iter().any(|(_, rule)| rule.good(*val)))
+}
+
+/// Rule for a field: its name and the ranges of valid values.
+#[derive(Debug)]
+pub struct Rule {
+    name: String,
+    ranges: Vec<(usize, usize)>,
+}
+
+impl FromStr for Rule {
+    type Err = AocError;
+    fn from_str(line: &str) -> Result<Rule> {
+        let (name, tail) = line
+            .split_once(':')
+            .ok_or_else(|| AocError::bad("rule without ':'", line.trim()).at_column(1))?;
+        let bound = |s: &str| parse_token(line, s.trim());
+        let ranges = tail
+            .split(" or ")
+            .map(|r| {
+                let (x, y) = r.split_once('-').ok_or_else(|| {
+                    AocError::bad("invalid range", r.trim()).at_token(line, r.trim())
+                })?;
+                Ok((bound(x)?, bound(y)?))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Rule {
+            name: name.to_string(),
+            ranges,
+        })
+    }
+}
+
+impl Rule {
+    /// Whether a value is valid for the field.
+    pub fn good(&self, n: usize) -> bool {
+        self.ranges.iter().any(|(l, u)| *l <= n && n <= *u)
+    }
+}
+
+fn get_invalid(rule: &Rule, ticket: &[usize]) -> Vec<usize> {
+    ticket.iter().filter(|&n| !rule.good(*n)).cloned().collect()
+}
+
+/// Read the rules, one per line.
+pub fn parse_rules(section: &Paragraph) -> Result<Rules> {
+    let rules: Vec<Rule> = section.parse_lines()?;
+    Ok(rules
+        .into_iter()
+        .map(|rule| (rule.name.clone(), rule))
+        .collect())
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Notes;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Notes> {
+        let sections = paragraphs(text);
+        if sections.len() != 3 {
+            return Err("expected rules, your ticket, and nearby tickets".into());
+        }
+        let rules = parse_rules(&sections[0])?;
+        let your = parse_tickets(&sections[1])?
+            .into_iter()
+            .next()
+            .ok_or("missing your ticket")?;
+        let nearby = parse_tickets(&sections[2])?;
+        Ok(Notes {
+            rules,
+            your,
+            nearby,
+        })
+    }
+
+    fn part1(&self, notes: &Notes) -> Result<usize> {
+        Ok(part1(&notes.rules, &notes.nearby))
+    }
+
+    fn part2(&self, notes: &Notes) -> Result<usize> {
+        part2(&notes.your, &notes.rules, &notes.nearby)
+            .ok_or_else(|| "no assignment of fields".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_valid_ticket() {
+        let text = "class: 1-3 or 5-7\nrow: 6-11 or 33-44\nseat: 13-40 or 45-50";
+        let rules = parse_rules(&paragraphs(text)[0]).unwrap();
+        assert!(is_valid_ticket(&rules, &[7, 3, 47]));
+        assert!(!is_valid_ticket(&rules, &[40, 4, 50]));
+        assert!(!is_valid_ticket(&rules, &[55, 2, 20]));
+        assert!(!is_valid_ticket(&rules, &[38, 6, 12]));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let text = "class: 1-3 or 5-7\n\nyour ticket:\n7,1\n\nnearby tickets:\n7,3\n40,x\n";
+        let err = Day16.parse(text).unwrap_err();
+        assert_eq!(err.to_string(), "8:4: invalid number 'x'");
+        let err = Day16
+            .parse("class: 1-3 or 5\n\nyour ticket:\n7\n\nnearby tickets:\n7\n")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "1:15: invalid range '5'");
+    }
+}
@@ -0,0 +1,131 @@
+//! Day 17: Conway Cubes
+
+use crate::{
+    error::{AocError, Result},
+    Solution,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Number of active cubes after six cycles in three dimensions.
+pub fn part1(mut active: HashSet<(isize, isize, isize)>) -> usize {
+    for _ in 0..6 {
+        evolve1(&mut active);
+    }
+    active.len()
+}
+
+/// Number of active cubes after six cycles in four dimensions.
+pub fn part2(mut active: HashSet<(isize, isize, isize, isize)>) -> usize {
+    for _ in 0..6 {
+        evolve2(&mut active);
+    }
+    active.len()
+}
+
+/// Run one cycle in three dimensions.
+pub fn evolve1(active: &mut HashSet<(isize, isize, isize)>) {
+    let mut count = HashMap::new();
+
+    // Count active neighbours
+    for (x, y, z) in active.iter() {
+        for i in x - 1..=x + 1 {
+            for j in y - 1..=y + 1 {
+                for k in z - 1..=z + 1 {
+                    *count.entry((i, j, k)).or_insert(0) += 1;
+                }
+            }
+        }
+        count.entry((*x, *y, *z)).and_modify(|e| *e -= 1);
+    }
+
+    let next = count
+        .iter()
+        .filter_map(|((x, y, z), cnt)| {
+            if active.contains(&(*x, *y, *z)) {
+                if *cnt == 2 || *cnt == 3 {
+                    Some((*x, *y, *z))
+                } else {
+                    None
+                }
+            } else if *cnt == 3 {
+                Some((*x, *y, *z))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    *active = next;
+}
+
+/// Run one cycle in four dimensions.
+pub fn evolve2(active: &mut HashSet<(isize, isize, isize, isize)>) {
+    let mut count = HashMap::new();
+
+    // Count active neighbours
+    for (w, x, y, z) in active.iter() {
+        for i in w - 1..=w + 1 {
+            for j in x - 1..=x + 1 {
+                for k in y - 1..=y + 1 {
+                    for l in z - 1..=z + 1 {
+                        *count.entry((i, j, k, l)).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        count.entry((*w, *x, *y, *z)).and_modify(|e| *e -= 1);
+    }
+    let next = count
+        .iter()
+        .filter_map(|((w, x, y, z), cnt)| {
+            if active.contains(&(*w, *x, *y, *z)) {
+                if *cnt == 2 || *cnt == 3 {
+                    Some((*w, *x, *y, *z))
+                } else {
+                    None
+                }
+            } else if *cnt == 3 {
+                Some((*w, *x, *y, *z))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    *active = next;
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    /// Coordinates of the active cubes in the initial slice.
+    type Input = Vec<(isize, isize)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Vec<(isize, isize)>> {
+        let mut active = Vec::new();
+        for (x, line) in text.lines().enumerate() {
+            for (y, ch) in line.chars().enumerate() {
+                match ch {
+                    '#' => active.push((x as isize, y as isize)),
+                    '.' => {}
+                    _ => {
+                        return Err(AocError::bad("unexpected character", &ch.to_string())
+                            .at_column(y + 1)
+                            .at_line(x + 1))
+                    }
+                }
+            }
+        }
+        Ok(active)
+    }
+
+    fn part1(&self, active: &Vec<(isize, isize)>) -> Result<usize> {
+        Ok(part1(active.iter().map(|&(x, y)| (x, y, 0)).collect()))
+    }
+
+    fn part2(&self, active: &Vec<(isize, isize)>) -> Result<usize> {
+        Ok(part2(active.iter().map(|&(x, y)| (x, y, 0, 0)).collect()))
+    }
+}
@@ -0,0 +1,178 @@
+//! Day 18: Operation Order
+
+use crate::{
+    error::{AocError, Result},
+    parse::parse_lines_with,
+    Solution,
+};
+
+/// Evaluate an expression where `+` and `*` have the same
+/// precedence.
+pub fn eval1(expr: &str) -> Option<isize> {
+    let tokens: Vec<char> = expr.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut stack: Vec<isize> = vec![];
+    let mut ops: Vec<char> = vec![];
+
+    for token in tokens {
+        match token {
+            '0'..='9' => {
+                stack.push(token.to_digit(10).unwrap() as isize);
+                apply_ops(&mut stack, &mut ops, |c| c == '(');
+            }
+            '+' | '*' | '(' => ops.push(token),
+            ')' => {
+                ops.pop();
+                apply_ops(&mut stack, &mut ops, |c| c == '(');
+            }
+            _ => todo!(),
+        }
+    }
+    stack.pop()
+}
+
+/// Evaluate an expression where `+` binds tighter than `*`.
+pub fn eval2(expr: &str) -> Option<isize> {
+    let tokens: Vec<char> = expr.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut stack: Vec<isize> = vec![];
+    let mut ops: Vec<char> = vec![];
+
+    for token in tokens {
+        match token {
+            '0'..='9' => {
+                stack.push(token.to_digit(10).unwrap() as isize);
+            }
+            '+' => {
+                apply_ops(&mut stack, &mut ops, |c| c == '(' || c == '*');
+                ops.push(token);
+            }
+            '*' => {
+                apply_ops(&mut stack, &mut ops, |c| c == '(');
+                ops.push(token);
+            }
+            '(' => {
+                ops.push(token);
+            }
+            ')' => {
+                apply_ops(&mut stack, &mut ops, |c| c == '(');
+                ops.pop();
+            }
+            _ => todo!(),
+        }
+    }
+    apply_ops(&mut stack, &mut ops, |_| false);
+    stack.pop()
+}
+
+fn apply_ops<F: Fn(char) -> bool>(stack: &mut Vec<isize>, ops: &mut Vec<char>, stop: F) {
+    while let Some(op) = ops.pop() {
+        if stop(op) {
+            ops.push(op);
+            break;
+        }
+        match op {
+            '+' => apply(stack, |x, y| x + y),
+            '*' => apply(stack, |x, y| x * y),
+            _ => todo!(),
+        }
+    }
+}
+
+fn apply<F: Fn(isize, isize) -> isize>(stack: &mut Vec<isize>, f: F) {
+    let lhs = stack.pop().unwrap();
+    let rhs = stack.pop().unwrap();
+    stack.push(f(lhs, rhs))
+}
+
+/// Check that an expression only has single digits, operators, and
+/// balanced parentheses, which is all the evaluators understand.
+fn check_expression(line: &str) -> Result<()> {
+    let mut open = vec![];
+    for (col, ch) in line.chars().enumerate() {
+        match ch {
+            '0'..='9' | '+' | '*' => {}
+            _ if ch.is_whitespace() => {}
+            '(' => open.push(col),
+            ')' => {
+                open.pop().ok_or_else(|| {
+                    AocError::bad("unbalanced parenthesis", ")").at_column(col + 1)
+                })?;
+            }
+            _ => {
+                return Err(
+                    AocError::bad("unexpected character", &ch.to_string()).at_column(col + 1)
+                )
+            }
+        }
+    }
+    match open.pop() {
+        Some(col) => Err(AocError::bad("unbalanced parenthesis", "(").at_column(col + 1)),
+        None => Ok(()),
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<String>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(&self, text: &str) -> Result<Vec<String>> {
+        parse_lines_with(text, |line| {
+            check_expression(line)?;
+            Ok(line.to_string())
+        })
+    }
+
+    fn part1(&self, equations: &Vec<String>) -> Result<isize> {
+        equations
+            .iter()
+            .map(|line| eval1(line))
+            .sum::<Option<isize>>()
+            .ok_or_else(|| "bad expression".into())
+    }
+
+    fn part2(&self, equations: &Vec<String>) -> Result<isize> {
+        equations
+            .iter()
+            .map(|line| eval2(line))
+            .sum::<Option<isize>>()
+            .ok_or_else(|| "bad expression".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval1() {
+        assert_eq!(eval1("1 + 2 * 3 + 4 * 5 + 6"), Some(71));
+        assert_eq!(eval1("1 + ( 2 * 3)"), Some(7));
+        assert_eq!(eval1("2 * 3 + (4 * 5)"), Some(26));
+        assert_eq!(eval1("5 + (8 * 3 + 9 + 3 * 4 * 3)"), Some(437));
+        assert_eq!(
+            eval1("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"),
+            Some(13632)
+        );
+    }
+    #[test]
+    fn test_eval2() {
+        assert_eq!(eval2("1 + 2 * 3 + 4 * 5 + 6"), Some(231));
+        assert_eq!(eval2("1 + ( 2 * 3)"), Some(7));
+        assert_eq!(eval2("2 * 3 + (4 * 5)"), Some(46));
+        assert_eq!(eval2("5 + (8 * 3 + 9 + 3 * 4 * 3)"), Some(1445));
+        assert_eq!(
+            eval2("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"),
+            Some(23340)
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day18.parse("1 + 2\n(3 * 4\n").unwrap_err();
+        assert_eq!(err.to_string(), "2:1: unbalanced parenthesis '('");
+        let err = Day18.parse("1 - 2\n").unwrap_err();
+        assert_eq!(err.to_string(), "1:3: unexpected character '-'");
+    }
+}
@@ -0,0 +1,157 @@
+//! Day 19: Monster Messages
+
+use crate::{
+    error::{AocError, Result},
+    parse::paragraphs,
+    Solution,
+};
+use regex::Regex;
+use std::collections::HashMap;
+
+/// The rules and the received messages.
+#[derive(Debug)]
+pub struct Messages {
+    rules: HashMap<usize, Body>,
+    messages: Vec<String>,
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Messages;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Messages> {
+        let sections = paragraphs(text);
+        if sections.len() != 2 {
+            return Err("expected rules and messages".into());
+        }
+        let rules = sections[0]
+            .parse_lines_with(from_str)?
+            .into_iter()
+            .collect();
+        let messages = sections[1]
+            .lines
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        Ok(Messages { rules, messages })
+    }
+
+    fn part1(&self, input: &Messages) -> Result<usize> {
+        part1(&input.rules, &input.messages).ok_or_else(|| "missing rules".into())
+    }
+
+    fn part2(&self, input: &Messages) -> Result<usize> {
+        part2(&input.rules, &input.messages).ok_or_else(|| "missing rules".into())
+    }
+}
+
+/// Number of messages that completely match rule 0.
+pub fn part1(rules: &HashMap<usize, Body>, messages: &[String]) -> Option<usize> {
+    let restr = format!("^{}$", build_regex(rules, 0)?);
+    let regex = Regex::new(&restr).unwrap();
+    Some(messages.iter().filter(|msg| regex.is_match(msg)).count())
+}
+
+/// Number of messages that completely match rule 0, with the loops
+/// in rules 8 and 11.
+//
+// For part 2, the rules 8 and 11 only exists at the top level in rule
+// "0: 8 11", which means that the smallest match is "42 42 31", next
+// is "42 42 42 31 31", etc. So we expand create a regexp that matches
+// a series of at lest one 42s and a series of at least one 31s and
+// ensure that there are more 42s than 31s (strictly).
+pub fn part2(rules: &HashMap<usize, Body>, messages: &[String]) -> Option<usize> {
+    let fortytwo = build_regex(rules, 42)?;
+    let thirtyone = build_regex(rules, 31)?;
+    let full = format!("^({}+)({}+)$", fortytwo, thirtyone);
+    let fortytwo_re = Regex::new(&fortytwo).unwrap();
+    let thirtyone_re = Regex::new(&thirtyone).unwrap();
+    let full_re = Regex::new(&full).unwrap();
+    let count = messages
+        .iter()
+        .filter(|msg| {
+            if let Some(cap) = full_re.captures(msg) {
+                let first = fortytwo_re.replace_all(cap.get(1).unwrap().as_str(), "1");
+                let second = thirtyone_re.replace_all(cap.get(2).unwrap().as_str(), "0");
+                first.len() > second.len()
+            } else {
+                false
+            }
+        })
+        .count();
+    Some(count)
+}
+
+/// Body of a rule: a single character, or alternative sequences of
+/// rules.
+#[derive(Debug)]
+pub enum Body {
+    Char(char),
+    Alt(Vec<Vec<usize>>),
+}
+
+fn from_str(line: &str) -> Result<(usize, Body)> {
+    let (no, body) = line
+        .split_once(':')
+        .ok_or_else(|| AocError::bad("rule without ':'", line.trim()).at_column(1))?;
+    let no = no
+        .trim()
+        .parse()
+        .map_err(|_| AocError::bad("invalid rule number", no.trim()).at_column(1))?;
+    let text = body.trim();
+    let body = if text.starts_with('"') {
+        let chars: Vec<char> = text.chars().collect();
+        match chars[..] {
+            ['"', ch, '"'] if ch.is_ascii_alphanumeric() => Body::Char(ch),
+            _ => return Err(AocError::bad("invalid character rule", text).at_token(line, text)),
+        }
+    } else {
+        let parts = body
+            .split('|')
+            .map(|p| {
+                p.split_whitespace()
+                    .map(|s| {
+                        s.parse()
+                            .map_err(|_| AocError::bad("invalid rule number", s).at_token(line, s))
+                    })
+                    .collect()
+            })
+            .collect::<Result<_>>()?;
+        Body::Alt(parts)
+    };
+    Ok((no, body))
+}
+
+/// Regular expression matching rule `start`, or `None` if a rule is
+/// missing.
+pub fn build_regex(rules: &HashMap<usize, Body>, start: usize) -> Option<String> {
+    match rules.get(&start)? {
+        Body::Alt(alts) => {
+            let alts = alts
+                .iter()
+                .map(|alt| {
+                    alt.iter()
+                        .map(|&rule| build_regex(rules, rule))
+                        .collect::<Option<Vec<_>>>()
+                        .map(|seq| seq.join(""))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("(?:{})", alts.join("|")))
+        }
+        Body::Char(ch) => Some(format!("{}", ch)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day19.parse("0: 1 2\n1: \"a\"\n2: 1 x\n\nab\n").unwrap_err();
+        assert_eq!(err.to_string(), "3:6: invalid rule number 'x'");
+    }
+}
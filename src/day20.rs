@@ -0,0 +1,84 @@
+//! Day 20: Jurassic Jigsaw
+//!
+//! The tiles of the image are read, but the puzzle is not solved yet,
+//! so the day is not in the registry.
+
+use crate::{
+    error::{AocError, Result},
+    parse::paragraphs,
+    Solution,
+};
+use regex::Regex;
+use std::collections::HashMap;
+
+/// A tile of the image, with its number and its pixels by row and
+/// column.
+#[derive(Debug)]
+pub struct Tile {
+    pub number: usize,
+    pub data: HashMap<(usize, usize), char>,
+}
+
+/// Read a tile, which starts with a `Tile N:` header.
+fn parse_tile(regex: &Regex, lines: &[&str]) -> Result<Tile> {
+    let cap = regex
+        .captures(lines[0])
+        .ok_or_else(|| AocError::bad("expected tile header", lines[0]).at_column(1))?;
+    let number = cap[1]
+        .parse()
+        .map_err(|_| AocError::bad("invalid tile number", &cap[1]).at_token(lines[0], &cap[1]))?;
+    let data = lines[1..]
+        .iter()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .map(move |(col, ch)| ((row, col), ch))
+        })
+        .collect();
+    Ok(Tile { number, data })
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Vec<Tile>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Vec<Tile>> {
+        let regex = Regex::new(r"^Tile (\d+):$").unwrap();
+        paragraphs(text)
+            .iter()
+            .map(|para| parse_tile(&regex, &para.lines).map_err(|err| err.at_line(para.first_line)))
+            .collect()
+    }
+
+    fn part1(&self, _tiles: &Vec<Tile>) -> Result<usize> {
+        Err(AocError::new("part 1 is not solved yet"))
+    }
+
+    fn part2(&self, _tiles: &Vec<Tile>) -> Result<usize> {
+        Err(AocError::new("part 2 is not solved yet"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let tiles = Day20
+            .parse("Tile 2311:\n..#\n##.\n\nTile 1951:\n#.#\n")
+            .unwrap();
+        assert_eq!(tiles.len(), 2);
+        assert_eq!(tiles[1].number, 1951);
+        assert_eq!(tiles[0].data[&(1, 0)], '#');
+
+        let err = Day20
+            .parse("Tile 2311:\n..#\n\nTile x:\n#.#\n")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "4:1: expected tile header 'Tile x:'");
+    }
+}
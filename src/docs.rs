@@ -0,0 +1,67 @@
+//! Extracting information from the module documentation of the days.
+//!
+//! The documentation of each day contains the puzzle description and
+//! the answers that were accepted, recorded as "Your puzzle answer
+//! was 692916." once for each part.
+
+use crate::expected::Expected;
+
+/// Module documentation of a source file, that is, the `//!`
+/// comments with the comment markers removed.
+pub fn module_docs(source: &str) -> String {
+    source
+        .lines()
+        .filter_map(|line| line.trim_start().strip_prefix("//!"))
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Answers recorded in the documentation. The first recorded answer
+/// is for part 1 and the second for part 2.
+pub fn recorded_answers(docs: &str) -> Expected {
+    let words: Vec<&str> = docs.split_whitespace().collect();
+    let mut answers = words
+        .windows(5)
+        .filter(|window| window[..4] == ["Your", "puzzle", "answer", "was"])
+        .map(|window| window[4].trim_end_matches('.').to_string());
+    Expected {
+        part1: answers.next(),
+        part2: answers.next(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "//! Day 1
+//!
+//! Your puzzle answer was
+//! 692916.
+//!
+//! # Part Two
+//!
+//!     Indented text.
+//!
+//! Your puzzle answer was 289270976.
+
+use std::error::Error;
+";
+
+    #[test]
+    fn test_module_docs() {
+        let docs = module_docs(SOURCE);
+        assert!(docs.starts_with("Day 1\n\nYour puzzle"));
+        assert!(docs.contains("\n    Indented text.\n"));
+        assert!(!docs.contains("use std"));
+    }
+
+    #[test]
+    fn test_recorded_answers() {
+        let expected = recorded_answers(&module_docs(SOURCE));
+        assert_eq!(expected.part1.as_deref(), Some("692916"));
+        assert_eq!(expected.part2.as_deref(), Some("289270976"));
+        assert_eq!(recorded_answers("No answers here."), Expected::default());
+    }
+}
@@ -0,0 +1,167 @@
+//! Error type used by all the days.
+//!
+//! Errors from parsing the input carry the location of the problem so
+//! that they can be reported as `aoc12.dat:17:1: unknown action 'X'`.
+//! The location is filled in gradually: the parser of a single line
+//! knows the column, the caller iterating over the lines knows the
+//! line number, and the runner knows the file name.
+
+use std::{convert::Infallible, error, fmt, num::ParseIntError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub text: Option<String>,
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    pub fn new<S: Into<String>>(message: S) -> AocError {
+        AocError {
+            message: message.into(),
+            file: None,
+            line: None,
+            column: None,
+            text: None,
+        }
+    }
+
+    /// Error for some offending text. The text is shown quoted after
+    /// the message.
+    pub fn bad<S: Into<String>>(message: S, text: &str) -> AocError {
+        AocError::new(message).with_text(text)
+    }
+
+    pub fn with_text(mut self, text: &str) -> AocError {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Set the line number (starting at 1), unless already set.
+    pub fn at_line(mut self, line: usize) -> AocError {
+        self.line.get_or_insert(line);
+        self
+    }
+
+    /// Set the column (starting at 1), unless already set.
+    pub fn at_column(mut self, column: usize) -> AocError {
+        self.column.get_or_insert(column);
+        self
+    }
+
+    /// Set the column to where `token` starts in `line`, unless
+    /// already set. The token has to be a slice of the line.
+    pub fn at_token(self, line: &str, token: &str) -> AocError {
+        self.at_column(column_of(line, token))
+    }
+
+    /// Set the file name, unless already set.
+    pub fn in_file(mut self, file: &str) -> AocError {
+        self.file.get_or_insert_with(|| file.to_string());
+        self
+    }
+}
+
+/// Column (starting at 1) where a slice of a line starts.
+pub fn column_of(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    // A token that is not part of the line gives a meaningless offset,
+    // which must not overflow.
+    let inside = offset
+        .checked_add(token.len())
+        .is_some_and(|end| end <= line.len());
+    if inside && line.is_char_boundary(offset) {
+        line[..offset].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+            if let Some(column) = self.column {
+                write!(f, "{}:", column)?;
+            }
+        }
+        if self.file.is_some() || self.line.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(text) = &self.text {
+            write!(f, " '{}'", text)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for AocError {}
+
+impl From<&str> for AocError {
+    fn from(message: &str) -> AocError {
+        AocError::new(message)
+    }
+}
+
+impl From<String> for AocError {
+    fn from(message: String) -> AocError {
+        AocError::new(message)
+    }
+}
+
+/// Numbers that do not parse are reported the same way, whatever the
+/// reason, and the caller adds the offending text.
+impl From<ParseIntError> for AocError {
+    fn from(_: ParseIntError) -> AocError {
+        AocError::new("invalid number")
+    }
+}
+
+impl From<Infallible> for AocError {
+    fn from(never: Infallible) -> AocError {
+        match never {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = AocError::bad("unknown action", "X")
+            .at_column(1)
+            .at_line(17)
+            .in_file("aoc12.dat");
+        assert_eq!(err.to_string(), "aoc12.dat:17:1: unknown action 'X'");
+        assert_eq!(AocError::new("no solution").to_string(), "no solution");
+        assert_eq!(
+            AocError::new("no solution").in_file("aoc1.dat").to_string(),
+            "aoc1.dat: no solution"
+        );
+    }
+
+    #[test]
+    fn test_location_kept() {
+        let err = AocError::new("oops").at_line(3).at_line(5).at_column(2);
+        assert_eq!((err.line, err.column), (Some(3), Some(2)));
+    }
+
+    #[test]
+    fn test_column_of() {
+        let line = "mem[8] = 11";
+        assert_eq!(column_of(line, &line[0..3]), 1);
+        assert_eq!(column_of(line, &line[9..]), 10);
+        assert_eq!(column_of(line, "elsewhere"), 1);
+        // A token starting before the line.
+        assert_eq!(column_of(&line[4..], &line[..6]), 1);
+    }
+}
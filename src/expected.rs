@@ -0,0 +1,110 @@
+//! Expected answers for an input.
+//!
+//! The expected answers are kept in a sidecar file next to the input,
+//! with the same name but the extension `.expected`, so the expected
+//! answers for `aoc7ex.dat` are in `aoc7ex.expected`. The file has
+//! one line per part, and parts without an expected answer are left
+//! out:
+//!
+//! ```text
+//! part1: 4
+//! part2: 32
+//! ```
+//!
+//! Empty lines and lines starting with `#` are ignored.
+
+use crate::Part;
+use std::{
+    fs::read_to_string,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Expected answers for the parts of a puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    /// Parse the contents of an expected-answer file.
+    pub fn parse(text: &str) -> Result<Expected, String> {
+        let mut expected = Expected::default();
+        for (lineno, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("line {}: expected 'partN: answer'", lineno + 1))?;
+            let slot = match key.trim() {
+                "part1" => &mut expected.part1,
+                "part2" => &mut expected.part2,
+                key => return Err(format!("line {}: unknown key '{}'", lineno + 1, key)),
+            };
+            *slot = Some(value.trim().to_string());
+        }
+        Ok(expected)
+    }
+
+    /// Read the expected answers from a file.
+    pub fn read(path: &Path) -> io::Result<Expected> {
+        let text = read_to_string(path)?;
+        Expected::parse(&text).map_err(|msg| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), msg),
+            )
+        })
+    }
+
+    /// Expected answer for a part, if there is one.
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    /// Parts that have an expected answer.
+    pub fn parts(&self) -> Vec<Part> {
+        [Part::One, Part::Two]
+            .iter()
+            .cloned()
+            .filter(|&part| self.get(part).is_some())
+            .collect()
+    }
+}
+
+/// Path of the expected-answer file for an input file.
+pub fn sidecar(input: &Path) -> PathBuf {
+    input.with_extension("expected")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let expected = Expected::parse("# Example 1\npart1: 514579\n\npart2:241861950\n").unwrap();
+        assert_eq!(expected.get(Part::One), Some("514579"));
+        assert_eq!(expected.get(Part::Two), Some("241861950"));
+
+        let expected = Expected::parse("part2: 8\n").unwrap();
+        assert_eq!(expected.parts(), vec![Part::Two]);
+
+        assert!(Expected::parse("part3: 8\n").is_err());
+        assert!(Expected::parse("8\n").is_err());
+    }
+
+    #[test]
+    fn test_sidecar() {
+        assert_eq!(
+            sidecar(Path::new("src/bin/aoc19ex2.dat")),
+            PathBuf::from("src/bin/aoc19ex2.expected")
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Downloading puzzle input.
+//!
+//! Downloaded input is stored as `aocN.dat` in the directory where
+//! new input of the year is stored (see [`Resolver::store_dir`]).
+//! Input that can already be found is never downloaded again, so the
+//! site is only asked once for each day.
+
+use crate::{
+    error::{AocError, Result},
+    input::{file_name, Resolver},
+    site::Client,
+};
+use std::{
+    fs::{create_dir_all, rename, write},
+    path::PathBuf,
+};
+
+/// Where the input of a day was found or stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Make sure the input of a day of a year is available, downloading
+/// it if it cannot be found.
+pub fn fetch(client: &Client, resolver: &Resolver, year: u32, day: u32) -> Result<Fetched> {
+    let name = file_name(day);
+    if let Some(path) = resolver
+        .candidates(year, &name)
+        .into_iter()
+        .find(|path| path.is_file())
+    {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let response = client.get(&client.day_url(year, day, "/input"))?;
+    if response.body.trim().is_empty() {
+        return Err(AocError::new(format!(
+            "empty input for day {} of {}",
+            day, year
+        )));
+    }
+
+    // Write to a temporary file first so that an interrupted download
+    // is not mistaken for a cached input.
+    let dir = resolver.store_dir(year);
+    let path = dir.join(&name);
+    let partial = dir.join(format!(".{}.part", name));
+    let io_error = |err| AocError::new(format!("{}: {}", path.display(), err));
+    create_dir_all(&dir).map_err(io_error)?;
+    write(&partial, &response.body).map_err(io_error)?;
+    rename(&partial, &path).map_err(io_error)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::tests::serve;
+    use std::{env, fs::read_to_string, process};
+
+    #[test]
+    fn test_fetch() {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}", process::id()));
+        let resolver = Resolver {
+            input_dir: Some(dir.join("inputs")),
+            default_dir: dir.join("src"),
+        };
+        let (url, server) = serve(vec![(200, "1721\n979\n"), (200, "199\n200\n")]);
+        let client = Client::new(&url, Some("abc"));
+
+        let path = dir.join("inputs").join("aoc1.dat");
+        assert_eq!(
+            fetch(&client, &resolver, 2020, 1),
+            Ok(Fetched::Downloaded(path.clone()))
+        );
+        assert_eq!(read_to_string(&path).unwrap(), "1721\n979\n");
+        // The server only answers twice, so this must come from the cache.
+        assert_eq!(
+            fetch(&client, &resolver, 2020, 1),
+            Ok(Fetched::Cached(path))
+        );
+        // Later years are kept apart.
+        assert_eq!(
+            fetch(&client, &resolver, 2021, 1),
+            Ok(Fetched::Downloaded(dir.join("inputs/2021/aoc1.dat")))
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2020/day/1/input "));
+        assert!(requests[0].contains("Cookie: session=abc\r\n"));
+        assert!(requests[1].starts_with("GET /2021/day/1/input "));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_failure() {
+        let dir = env::temp_dir().join(format!("aoc-fetch-fail-{}", process::id()));
+        let resolver = Resolver {
+            input_dir: Some(dir.clone()),
+            default_dir: dir.clone(),
+        };
+        let (url, server) = serve(vec![(404, "Please don't repeatedly request this endpoint")]);
+        let client = Client::new(&url, Some("abc"));
+        let err = fetch(&client, &resolver, 2020, 25).unwrap_err();
+        assert!(err.message.contains("HTTP status 404"), "{}", err);
+        assert!(!dir.join("aoc25.dat").exists());
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,872 @@
+//! Generated puzzle input.
+//!
+//! Each day of 2020 has a generator making input in the format of the
+//! puzzle, for stress testing and benchmarking the solvers on input
+//! larger than the puzzle input. The input is made from a seed, and
+//! the same seed always gives the same input.
+//!
+//! What the size counts depends on the day: entries, lines, groups,
+//! bags, or the side of a grid (see [`GENERATORS`]). Where it is
+//! cheap, the input also has the structure the puzzle promises, such
+//! as a missing seat in day 5 or a program that can be repaired in
+//! day 8, so that both parts have an answer. The answers can still
+//! overflow for large sizes, like the number of arrangements of the
+//! adapters in day 10.
+
+use crate::{
+    day01,
+    day08::{self, Instr, Machine},
+    day09::{self, WINDOW},
+    error::{AocError, Result},
+    random::Rng,
+};
+use std::{collections::HashSet, ops::RangeInclusive};
+
+/// Generator of input for a day.
+pub struct Generator {
+    pub year: u32,
+    pub day: u32,
+    /// What the size counts.
+    pub unit: &'static str,
+    /// Size used when none is given, close to that of the puzzle
+    /// input.
+    pub default_size: usize,
+    pub sizes: RangeInclusive<usize>,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+/// Generators for all days, ordered by year and day.
+pub static GENERATORS: [Generator; 20] = [
+    Generator {
+        year: 2020,
+        day: 1,
+        unit: "entries",
+        default_size: 200,
+        sizes: 1..=100_000,
+        generate: expense_report,
+    },
+    Generator {
+        year: 2020,
+        day: 2,
+        unit: "passwords",
+        default_size: 1000,
+        sizes: 1..=1_000_000,
+        generate: passwords,
+    },
+    Generator {
+        year: 2020,
+        day: 3,
+        unit: "rows",
+        default_size: 323,
+        sizes: 1..=1_000_000,
+        generate: slope,
+    },
+    Generator {
+        year: 2020,
+        day: 4,
+        unit: "passports",
+        default_size: 290,
+        sizes: 1..=1_000_000,
+        generate: passports,
+    },
+    Generator {
+        year: 2020,
+        day: 5,
+        unit: "boarding passes",
+        default_size: 800,
+        sizes: 1..=1000,
+        generate: boarding_passes,
+    },
+    Generator {
+        year: 2020,
+        day: 6,
+        unit: "groups",
+        default_size: 490,
+        sizes: 1..=1_000_000,
+        generate: customs_answers,
+    },
+    Generator {
+        year: 2020,
+        day: 7,
+        unit: "bags",
+        default_size: 594,
+        sizes: 1..=100_000,
+        generate: bag_rules,
+    },
+    Generator {
+        year: 2020,
+        day: 8,
+        unit: "instructions",
+        default_size: 650,
+        sizes: 1..=100_000,
+        generate: boot_code,
+    },
+    Generator {
+        year: 2020,
+        day: 9,
+        unit: "numbers",
+        default_size: 1000,
+        // The numbers grow with each step and would overflow beyond
+        // this.
+        sizes: WINDOW + 1..=1000,
+        generate: xmas_data,
+    },
+    Generator {
+        year: 2020,
+        day: 10,
+        unit: "adapters",
+        default_size: 100,
+        sizes: 1..=100_000,
+        generate: adapters,
+    },
+    Generator {
+        year: 2020,
+        day: 11,
+        unit: "rows and columns",
+        default_size: 95,
+        sizes: 1..=2000,
+        generate: seat_layout,
+    },
+    Generator {
+        year: 2020,
+        day: 12,
+        unit: "actions",
+        default_size: 780,
+        sizes: 1..=1_000_000,
+        generate: navigation,
+    },
+    Generator {
+        year: 2020,
+        day: 13,
+        unit: "schedule entries",
+        default_size: 60,
+        sizes: 1..=100_000,
+        generate: bus_notes,
+    },
+    Generator {
+        year: 2020,
+        day: 14,
+        unit: "instructions",
+        default_size: 560,
+        sizes: 1..=100_000,
+        generate: docking_program,
+    },
+    Generator {
+        year: 2020,
+        day: 15,
+        unit: "starting numbers",
+        default_size: 6,
+        sizes: 1..=1000,
+        generate: starting_numbers,
+    },
+    Generator {
+        year: 2020,
+        day: 16,
+        unit: "fields",
+        default_size: 20,
+        sizes: 1..=500,
+        generate: ticket_notes,
+    },
+    Generator {
+        year: 2020,
+        day: 17,
+        unit: "rows and columns",
+        default_size: 8,
+        sizes: 1..=100,
+        generate: cubes,
+    },
+    Generator {
+        year: 2020,
+        day: 18,
+        unit: "expressions",
+        default_size: 370,
+        sizes: 1..=1_000_000,
+        generate: expressions,
+    },
+    Generator {
+        year: 2020,
+        day: 19,
+        unit: "messages",
+        default_size: 400,
+        sizes: 1..=100_000,
+        generate: messages,
+    },
+    Generator {
+        year: 2020,
+        day: 20,
+        unit: "tiles per side",
+        default_size: 12,
+        // Tile numbers have four digits.
+        sizes: 1..=94,
+        generate: tiles,
+    },
+];
+
+/// Find the generator for a day of a year.
+pub fn find(year: u32, day: u32) -> Option<&'static Generator> {
+    GENERATORS
+        .iter()
+        .find(|generator| generator.year == year && generator.day == day)
+}
+
+impl Generator {
+    /// Generate input of the given size, or the default size, from a
+    /// seed.
+    pub fn generate(&self, size: Option<usize>, seed: u64) -> Result<String> {
+        let size = size.unwrap_or(self.default_size);
+        if !self.sizes.contains(&size) {
+            return Err(AocError::new(format!(
+                "size for day {} has to be from {} to {} {}",
+                self.day,
+                self.sizes.start(),
+                self.sizes.end(),
+                self.unit
+            )));
+        }
+        Ok((self.generate)(&mut Rng::new(seed), size))
+    }
+}
+
+/// One item per line.
+fn lines<T: ToString>(items: impl IntoIterator<Item = T>) -> String {
+    items
+        .into_iter()
+        .map(|item| item.to_string() + "\n")
+        .collect()
+}
+
+/// Grid of `#` with the given probability and `other` otherwise.
+fn grid(rng: &mut Rng, rows: usize, cols: usize, probability: f64, other: char) -> String {
+    lines((0..rows).map(|_| {
+        (0..cols)
+            .map(|_| if rng.chance(probability) { '#' } else { other })
+            .collect::<String>()
+    }))
+}
+
+const CONSONANTS: &[u8] = b"bcdfghklmnprstvz";
+const VOWELS: &[u8] = b"aeiou";
+
+/// Made-up word that does not mean anything.
+fn word(rng: &mut Rng) -> String {
+    let syllables = rng.range(2, 3);
+    (0..syllables)
+        .flat_map(|_| vec![*rng.pick(CONSONANTS) as char, *rng.pick(VOWELS) as char])
+        .collect()
+}
+
+/// Made-up words, all different.
+fn words(rng: &mut Rng, count: usize) -> Vec<String> {
+    let mut taken = HashSet::new();
+    let mut words = vec![];
+    while words.len() < count {
+        let word = word(rng);
+        if taken.insert(word.clone()) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// Day 1: entries, with two that sum to 2020 and three that do.
+fn expense_report(rng: &mut Rng, size: usize) -> String {
+    let mut entries = day01::generate(rng, size.saturating_sub(5));
+    if size >= 5 {
+        let a = rng.range(1, 2019) as u32;
+        let b = rng.range(1, 1009) as u32;
+        let c = rng.range(1, 2019 - b as i64) as u32;
+        entries.extend([a, 2020 - a, b, c, 2020 - b - c]);
+        rng.shuffle(&mut entries);
+    }
+    lines(entries)
+}
+
+/// Day 2: password policies and passwords.
+fn passwords(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let letter = (b'a' + rng.below(26) as u8) as char;
+        let len = rng.range(1, 16);
+        let password: String = (0..len)
+            .map(|_| match rng.chance(0.3) {
+                true => letter,
+                false => (b'a' + rng.below(26) as u8) as char,
+            })
+            .collect();
+        let low = rng.range(1, len);
+        let high = rng.range(low, len);
+        format!("{}-{} {}: {}", low, high, letter, password)
+    }))
+}
+
+/// Day 3: map of trees.
+fn slope(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size, 31, 0.25, '.')
+}
+
+/// Value of a passport field, which is valid or not.
+fn passport_field(rng: &mut Rng, key: &str, valid: bool) -> String {
+    let year = |rng: &mut Rng, (low, high), (bad_low, bad_high)| match valid {
+        true => rng.range(low, high).to_string(),
+        false => rng.range(bad_low, bad_high).to_string(),
+    };
+    let hex = |rng: &mut Rng| format!("{:06x}", rng.below(1 << 24));
+    match (key, valid) {
+        ("byr", _) => year(rng, (1920, 2002), (2003, 2030)),
+        ("iyr", _) => year(rng, (2010, 2020), (1990, 2009)),
+        ("eyr", _) => year(rng, (2020, 2030), (2031, 2040)),
+        ("hgt", true) if rng.chance(0.5) => format!("{}cm", rng.range(150, 193)),
+        ("hgt", true) => format!("{}in", rng.range(59, 76)),
+        ("hgt", false) => rng.range(50, 200).to_string(),
+        ("hcl", true) => format!("#{}", hex(rng)),
+        ("hcl", false) => hex(rng),
+        ("ecl", true) => rng
+            .pick(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"])
+            .to_string(),
+        ("ecl", false) => rng.pick(&["xry", "zzz", "gmt"]).to_string(),
+        ("pid", true) => format!("{:09}", rng.below(1_000_000_000)),
+        ("pid", false) => format!("{:08}", rng.below(100_000_000)),
+        _ => rng.range(100, 350).to_string(),
+    }
+}
+
+/// Day 4: passports with fields that may be missing or invalid,
+/// separated by blank lines.
+fn passports(rng: &mut Rng, size: usize) -> String {
+    let passports: Vec<String> = (0..size)
+        .map(|_| {
+            let mut fields = vec![];
+            for key in &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"] {
+                if rng.chance(0.9) {
+                    let valid = rng.chance(0.9);
+                    fields.push(format!("{}:{}", key, passport_field(rng, key, valid)));
+                }
+            }
+            rng.shuffle(&mut fields);
+            let mut text = String::new();
+            for (index, field) in fields.iter().enumerate() {
+                if index > 0 {
+                    text.push(if rng.chance(0.3) { '\n' } else { ' ' });
+                }
+                text.push_str(field);
+            }
+            text + "\n"
+        })
+        .collect();
+    passports.join("\n")
+}
+
+/// Day 5: boarding passes for consecutive seats, except for one.
+fn boarding_passes(rng: &mut Rng, size: usize) -> String {
+    let first = rng.range(0, (1023 - size) as i64) as u32;
+    let mut seats: Vec<u32> = (first..=first + size as u32).collect();
+    if size > 1 {
+        seats.remove(rng.range(1, size as i64 - 1) as usize);
+    } else {
+        seats.pop();
+    }
+    rng.shuffle(&mut seats);
+    lines(seats.into_iter().map(|seat| {
+        (0..10)
+            .rev()
+            .map(|bit| match (bit >= 3, seat >> bit & 1) {
+                (true, 0) => 'F',
+                (true, _) => 'B',
+                (false, 0) => 'L',
+                (false, _) => 'R',
+            })
+            .collect::<String>()
+    }))
+}
+
+/// Day 6: answers of groups, separated by blank lines.
+fn customs_answers(rng: &mut Rng, size: usize) -> String {
+    let groups: Vec<String> = (0..size)
+        .map(|_| {
+            let questions: Vec<char> = ('a'..='z').filter(|_| rng.chance(0.4)).collect();
+            let people = rng.range(1, 5);
+            lines((0..people).map(|_| {
+                let mut answers: String = questions.iter().filter(|_| rng.chance(0.7)).collect();
+                if answers.is_empty() {
+                    answers.push((b'a' + rng.below(26) as u8) as char);
+                }
+                answers
+            }))
+        })
+        .collect();
+    groups.join("\n")
+}
+
+/// Day 7: rules for bags, including a shiny gold one. Bags only
+/// contain bags of a lower level, so that there are no cycles and the
+/// number of bags inside a bag stays reasonable.
+fn bag_rules(rng: &mut Rng, size: usize) -> String {
+    const LEVELS: i64 = 6;
+    let mut names: Vec<String> = vec!["shiny gold".to_string()];
+    let mut taken = HashSet::new();
+    while names.len() < size {
+        let name = format!("{} {}", word(rng), word(rng));
+        if taken.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    // The shiny gold bag is in the middle, so that both parts have
+    // something to count.
+    let bags: Vec<(String, i64)> = names
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| match index {
+            0 => (name, LEVELS / 2),
+            _ => (name, rng.range(0, LEVELS)),
+        })
+        .collect();
+    let mut rules: Vec<String> = bags
+        .iter()
+        .map(|(name, level)| {
+            let lower: Vec<&String> = bags
+                .iter()
+                .filter(|(_, other)| other < level)
+                .map(|(name, _)| name)
+                .collect();
+            let count = match lower.is_empty() {
+                true => 0,
+                false => rng.range(0, 4),
+            };
+            let mut inside = HashSet::new();
+            let mut contents = vec![];
+            for _ in 0..count {
+                let name = *rng.pick(&lower);
+                if inside.insert(name) {
+                    contents.push(match rng.range(1, 5) {
+                        1 => format!("1 {} bag", name),
+                        n => format!("{} {} bags", n, name),
+                    });
+                }
+            }
+            match contents.is_empty() {
+                true => format!("{} bags contain no other bags.", name),
+                false => format!("{} bags contain {}.", name, contents.join(", ")),
+            }
+        })
+        .collect();
+    rng.shuffle(&mut rules);
+    lines(rules)
+}
+
+/// Day 8: a program that loops and can be repaired, unless none is
+/// found in a number of tries.
+fn boot_code(rng: &mut Rng, size: usize) -> String {
+    let mut program = day08::generate(rng, size);
+    for _ in 0..100 {
+        if !Machine::default().run(&program) && day08::repair(&program).is_some() {
+            break;
+        }
+        program = day08::generate(rng, size);
+    }
+    lines(program.iter().map(|instr| match instr {
+        Instr::Acc(val) => format!("acc {:+}", val),
+        Instr::Jmp(val) => format!("jmp {:+}", val),
+        Instr::Nop(val) => format!("nop {:+}", val),
+    }))
+}
+
+/// A number that is not the sum of two of the numbers in the window,
+/// made from a contiguous range of earlier numbers where possible.
+fn invalid_number(rng: &mut Rng, numbers: &[usize]) -> usize {
+    let window = &numbers[numbers.len() - WINDOW..];
+    for _ in 0..100 {
+        let len = rng.range(2, 10) as usize;
+        let start = rng.range(0, (numbers.len() - len) as i64) as usize;
+        let sum = numbers[start..start + len].iter().sum();
+        if day09::is_invalid(sum, window) {
+            return sum;
+        }
+    }
+    // All numbers are positive, so this is larger than any two.
+    window.iter().sum()
+}
+
+/// Day 9: numbers that are each the sum of two of the numbers before
+/// them, except for one.
+fn xmas_data(rng: &mut Rng, size: usize) -> String {
+    let mut preamble: Vec<usize> = (1..=100).collect();
+    rng.shuffle(&mut preamble);
+    let mut numbers = preamble[..WINDOW].to_vec();
+    let invalid = rng.range(WINDOW.max(size / 2) as i64, size as i64 - 1) as usize;
+    for index in WINDOW..size {
+        if index == invalid {
+            let number = invalid_number(rng, &numbers);
+            numbers.push(number);
+            continue;
+        }
+        // Adding the smaller numbers keeps them from growing too fast.
+        let mut window = numbers[index - WINDOW..index].to_vec();
+        window.sort_unstable();
+        let first = rng.below(8) as usize;
+        let second = (first + 1 + rng.below(7) as usize) % 8;
+        numbers.push(window[first] + window[second]);
+    }
+    lines(numbers)
+}
+
+/// Day 10: adapters that differ by 1 or 3 jolts, in random order.
+/// Runs of adapters that differ by 1 are kept short, like in the
+/// puzzle input.
+fn adapters(rng: &mut Rng, size: usize) -> String {
+    let mut jolts = vec![];
+    let mut jolt = 0;
+    let mut run = 0;
+    for _ in 0..size {
+        if run < 4 && rng.chance(0.6) {
+            jolt += 1;
+            run += 1;
+        } else {
+            jolt += 3;
+            run = 0;
+        }
+        jolts.push(jolt);
+    }
+    rng.shuffle(&mut jolts);
+    lines(jolts)
+}
+
+/// Day 11: empty seats and floor.
+fn seat_layout(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size, size, 0.0, '.')
+        .chars()
+        .map(|ch| match ch {
+            '.' if rng.chance(0.8) => 'L',
+            ch => ch,
+        })
+        .collect()
+}
+
+/// Day 12: navigation instructions.
+fn navigation(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| match *rng.pick(b"NSEWLRF") {
+        turn @ (b'L' | b'R') => format!("{}{}", turn as char, rng.range(1, 3) * 90),
+        action => format!("{}{}", action as char, rng.range(1, 99)),
+    }))
+}
+
+/// Day 13: timestamp and bus lines, which are different primes so
+/// that the departures can be lined up.
+fn bus_notes(rng: &mut Rng, size: usize) -> String {
+    let mut primes = vec![5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61];
+    rng.shuffle(&mut primes);
+    let buses = (size / 4).clamp(1, 9);
+    let mut positions: Vec<usize> = (1..size).collect();
+    rng.shuffle(&mut positions);
+    positions.truncate(buses - 1);
+    positions.push(0);
+    let mut schedule = vec!["x".to_string(); size];
+    for (&position, bus) in positions.iter().zip(primes) {
+        schedule[position] = bus.to_string();
+    }
+    format!(
+        "{}\n{}\n",
+        rng.range(100_000, 1_000_000),
+        schedule.join(",")
+    )
+}
+
+/// Day 14: masks and writes to memory, with at most 9 floating bits
+/// in a mask.
+fn docking_program(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|index| {
+        if index == 0 || rng.chance(0.15) {
+            let mut mask: Vec<char> = (0..36)
+                .map(|_| if rng.chance(0.5) { '1' } else { '0' })
+                .collect();
+            for _ in 0..rng.range(0, 9) {
+                mask[rng.below(36) as usize] = 'X';
+            }
+            format!("mask = {}", mask.into_iter().collect::<String>())
+        } else {
+            format!("mem[{}] = {}", rng.below(1 << 16), rng.below(1_000_000_000))
+        }
+    }))
+}
+
+/// Day 15: different starting numbers.
+fn starting_numbers(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<usize> = (0..size * 3).collect();
+    rng.shuffle(&mut numbers);
+    let numbers: Vec<String> = numbers[..size].iter().map(usize::to_string).collect();
+    numbers.join(",") + "\n"
+}
+
+/// Day 16: rules for fields, your ticket, and nearby tickets, of which
+/// about a quarter are invalid.
+fn ticket_notes(rng: &mut Rng, size: usize) -> String {
+    let departures = size * 3 / 10;
+    let names: Vec<String> = words(rng, size)
+        .into_iter()
+        .enumerate()
+        .map(|(index, word)| match index < departures {
+            true => format!("departure {}", word),
+            false => word,
+        })
+        .collect();
+    let ranges: Vec<[(i64, i64); 2]> = (0..size)
+        .map(|_| {
+            let a = rng.range(1, 100);
+            let b = rng.range(a + 1, 500);
+            let c = rng.range(b + 2, 800);
+            let d = rng.range(c + 1, 974);
+            [(a, b), (c, d)]
+        })
+        .collect();
+    let mut columns: Vec<usize> = (0..size).collect();
+    rng.shuffle(&mut columns);
+    let ticket = |rng: &mut Rng, valid: bool| {
+        let mut values: Vec<i64> = columns
+            .iter()
+            .map(|&field| {
+                let (low, high) = *rng.pick(&ranges[field]);
+                rng.range(low, high)
+            })
+            .collect();
+        if !valid {
+            values[rng.below(size as u64) as usize] = rng.range(975, 999);
+        }
+        let values: Vec<String> = values.iter().map(i64::to_string).collect();
+        values.join(",")
+    };
+    let rules: Vec<String> = names
+        .iter()
+        .zip(&ranges)
+        .map(|(name, [(a, b), (c, d)])| format!("{}: {}-{} or {}-{}", name, a, b, c, d))
+        .collect();
+    let your = ticket(rng, true);
+    let nearby: Vec<String> = (0..size * 12)
+        .map(|_| {
+            let valid = rng.chance(0.75);
+            ticket(rng, valid)
+        })
+        .collect();
+    format!(
+        "{}\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+        lines(rules),
+        your,
+        lines(nearby)
+    )
+}
+
+/// Day 17: active and inactive cubes.
+fn cubes(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size, size, 0.5, '.')
+}
+
+/// Expression with at most `leaves` numbers, and parentheses nested at
+/// most `depth` deep.
+fn expression(rng: &mut Rng, leaves: &mut usize, depth: usize) -> String {
+    let terms = rng.range(2, 4);
+    let mut text = String::new();
+    for index in 0..terms {
+        if *leaves == 0 {
+            break;
+        }
+        if index > 0 {
+            text.push_str(if rng.chance(0.5) { " + " } else { " * " });
+        }
+        if depth > 0 && *leaves > 2 && rng.chance(0.3) {
+            text.push_str(&format!("({})", expression(rng, leaves, depth - 1)));
+        } else {
+            text.push_str(&rng.range(1, 9).to_string());
+            *leaves -= 1;
+        }
+    }
+    text
+}
+
+/// Day 18: expressions, small enough that the results do not
+/// overflow.
+fn expressions(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| expression(rng, &mut 12, 3)))
+}
+
+/// A message matching the rule.
+fn sample(
+    rng: &mut Rng,
+    rules: &[(usize, Vec<Vec<usize>>)],
+    letters: &[(usize, char)],
+    rule: usize,
+) -> String {
+    if let Some(&(_, letter)) = letters.iter().find(|(number, _)| *number == rule) {
+        return letter.to_string();
+    }
+    let (_, alternatives) = rules.iter().find(|(number, _)| *number == rule).unwrap();
+    rng.pick(alternatives)
+        .iter()
+        .map(|&part| sample(rng, rules, letters, part))
+        .collect()
+}
+
+/// Day 19: rules and messages. Rule 0 is `8 11`, with 8 and 11 built
+/// from 42 and 31 like in the puzzle, and the other rules have
+/// alternatives of two rules of the level below, so that all messages
+/// matching a rule have the same length. About half of the messages
+/// match the rules of part 2.
+fn messages(rng: &mut Rng, size: usize) -> String {
+    const LEVELS: usize = 3;
+    let mut numbers: Vec<usize> = (1..130).filter(|n| ![8, 11, 31, 42].contains(n)).collect();
+    rng.shuffle(&mut numbers);
+    let letters = [(numbers[0], 'a'), (numbers[1], 'b')];
+    let mut next = 2;
+    let mut level: Vec<usize> = letters.iter().map(|&(number, _)| number).collect();
+    let mut rules: Vec<(usize, Vec<Vec<usize>>)> = vec![];
+    for depth in 1..=LEVELS {
+        let count = if depth == LEVELS {
+            2
+        } else {
+            rng.range(2, 4) as usize
+        };
+        let mut above = vec![];
+        for index in 0..count {
+            let number = match (depth == LEVELS, index) {
+                (true, 0) => 42,
+                (true, _) => 31,
+                _ => {
+                    next += 1;
+                    numbers[next - 1]
+                }
+            };
+            let alternatives = (0..rng.range(1, 2))
+                .map(|_| vec![*rng.pick(&level), *rng.pick(&level)])
+                .collect();
+            rules.push((number, alternatives));
+            above.push(number);
+        }
+        level = above;
+    }
+    rules.push((0, vec![vec![8, 11]]));
+    rules.push((8, vec![vec![42]]));
+    rules.push((11, vec![vec![42, 31]]));
+
+    let chunk = 1 << LEVELS;
+    let messages: Vec<String> = (0..size)
+        .map(|_| {
+            if rng.chance(0.5) {
+                let thirtyones = rng.range(1, 3);
+                let fortytwos = rng.range(thirtyones + 1, thirtyones + 3);
+                let mut message = String::new();
+                for _ in 0..fortytwos {
+                    message.push_str(&sample(rng, &rules, &letters, 42));
+                }
+                for _ in 0..thirtyones {
+                    message.push_str(&sample(rng, &rules, &letters, 31));
+                }
+                message
+            } else {
+                (0..chunk * rng.range(2, 6) as usize)
+                    .map(|_| if rng.chance(0.5) { 'a' } else { 'b' })
+                    .collect()
+            }
+        })
+        .collect();
+
+    let mut lines_of_rules: Vec<String> = letters
+        .iter()
+        .map(|(number, letter)| format!("{}: \"{}\"", number, letter))
+        .chain(rules.iter().map(|(number, alternatives)| {
+            let alternatives: Vec<String> = alternatives
+                .iter()
+                .map(|parts| {
+                    let parts: Vec<String> = parts.iter().map(usize::to_string).collect();
+                    parts.join(" ")
+                })
+                .collect();
+            format!("{}: {}", number, alternatives.join(" | "))
+        }))
+        .collect();
+    rng.shuffle(&mut lines_of_rules);
+    format!("{}\n{}", lines(lines_of_rules), lines(messages))
+}
+
+/// Rotate a square tile a quarter turn clockwise.
+fn rotate(tile: &[Vec<char>]) -> Vec<Vec<char>> {
+    let size = tile.len();
+    (0..size)
+        .map(|row| (0..size).map(|col| tile[size - 1 - col][row]).collect())
+        .collect()
+}
+
+/// Day 20: tiles cut from an image, which share their borders with
+/// their neighbours, and are rotated and flipped at random.
+fn tiles(rng: &mut Rng, size: usize) -> String {
+    let side = 9 * size + 1;
+    let image: Vec<Vec<char>> = (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| if rng.chance(0.5) { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+    let mut numbers: Vec<usize> = (1000..10000).collect();
+    rng.shuffle(&mut numbers);
+    let mut tiles: Vec<String> = (0..size * size)
+        .map(|index| {
+            let (top, left) = (9 * (index / size), 9 * (index % size));
+            let mut tile: Vec<Vec<char>> = image[top..top + 10]
+                .iter()
+                .map(|row| row[left..left + 10].to_vec())
+                .collect();
+            for _ in 0..rng.below(4) {
+                tile = rotate(&tile);
+            }
+            if rng.chance(0.5) {
+                tile.reverse();
+            }
+            let rows = tile.iter().map(|row| row.iter().collect::<String>());
+            format!("Tile {}:\n{}", numbers[index], lines(rows))
+        })
+        .collect();
+    rng.shuffle(&mut tiles);
+    tiles.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day20::Day20, registry, runner, Part, Solution};
+
+    #[test]
+    fn test_generators() {
+        for generator in &GENERATORS {
+            let text = generator.generate(None, 1).unwrap();
+            assert_eq!(text, generator.generate(None, 1).unwrap());
+            assert_ne!(text, generator.generate(None, 2).unwrap());
+            assert!(text.ends_with('\n') && !text.ends_with("\n\n"));
+            match registry::find(generator.year, generator.day) {
+                // Day 15 is too slow to solve in a test.
+                Some(day) if day.number != 15 => {
+                    for outcome in runner::run(day, &text, &[Part::One, Part::Two]) {
+                        assert!(
+                            outcome.is_ok(),
+                            "day {} part {}: {}",
+                            day.number,
+                            outcome.part,
+                            outcome.result.unwrap_err()
+                        );
+                    }
+                }
+                Some(day) => assert!(day.solver.parse_input(&text).is_ok()),
+                None => assert_eq!(Day20.parse(&text).unwrap().len(), 144),
+            }
+        }
+    }
+
+    #[test]
+    fn test_sizes() {
+        let day9 = find(2020, 9).unwrap();
+        assert!(day9.generate(Some(10), 1).is_err());
+        let text = day9.generate(Some(30), 1).unwrap();
+        assert_eq!(text.lines().count(), 30);
+        assert_eq!(
+            find(2020, 5).unwrap().generate(Some(1), 7).unwrap().len(),
+            11
+        );
+        assert!(find(2020, 21).is_none());
+        assert!(find(2021, 1).is_none());
+    }
+}
@@ -0,0 +1,297 @@
+//! Minimal HTTP client for talking to the puzzle site.
+//!
+//! Plain `http://` URLs are handled directly over a TCP connection,
+//! which is what the tests use with a local server. The standard
+//! library has no TLS, so `https://` URLs are handed to `curl`. The
+//! request is given to `curl` as a config on standard input, which
+//! keeps the session cookie out of the process list.
+
+use crate::error::{AocError, Result};
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+/// How long to wait for the server before giving up.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Parts of an `http://` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Url<'a> {
+    host: &'a str,
+    port: u16,
+    path: &'a str,
+}
+
+fn parse_url(url: &str) -> Result<Url<'_>> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| AocError::bad("unsupported URL", url))?;
+    let (authority, path) = match rest.find('/') {
+        Some(pos) => rest.split_at(pos),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => {
+            let port = port
+                .parse()
+                .map_err(|_| AocError::bad("bad port in URL", url))?;
+            (host, port)
+        }
+        None => (authority, 80),
+    };
+    if host.is_empty() {
+        return Err(AocError::bad("no host in URL", url));
+    }
+    Ok(Url { host, port, path })
+}
+
+impl Url<'_> {
+    /// Value of the `Host` header: the host, with the port unless it
+    /// is the default one.
+    fn host_header(&self) -> String {
+        match self.port {
+            80 => self.host.to_string(),
+            port => format!("{}:{}", self.host, port),
+        }
+    }
+}
+
+/// Send a request and return the response. Headers are given as
+/// name-value pairs and a body, if any, is sent as a form.
+pub fn request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Result<Response> {
+    if url.starts_with("https://") {
+        curl(method, url, headers, body)
+    } else {
+        plain(method, url, headers, body)
+    }
+}
+
+pub fn get(url: &str, headers: &[(&str, &str)]) -> Result<Response> {
+    request("GET", url, headers, None)
+}
+
+pub fn post(url: &str, headers: &[(&str, &str)], body: &str) -> Result<Response> {
+    request("POST", url, headers, Some(body))
+}
+
+fn io_error(url: &str) -> impl Fn(std::io::Error) -> AocError + '_ {
+    move |err| AocError::new(format!("{}: {}", url, err))
+}
+
+fn plain(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Result<Response> {
+    let parsed = parse_url(url)?;
+    let mut stream = TcpStream::connect((parsed.host, parsed.port)).map_err(io_error(url))?;
+    stream
+        .set_read_timeout(Some(TIMEOUT))
+        .map_err(io_error(url))?;
+
+    // HTTP/1.0 keeps the response simple: no chunked encoding, and the
+    // body ends when the server closes the connection.
+    let mut request = format!(
+        "{} {} HTTP/1.0\r\nHost: {}\r\n",
+        method,
+        parsed.path,
+        parsed.host_header()
+    );
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    if let Some(body) = body {
+        request.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(body.unwrap_or(""));
+    stream
+        .write_all(request.as_bytes())
+        .map_err(io_error(url))?;
+
+    let mut raw = vec![];
+    stream.read_to_end(&mut raw).map_err(io_error(url))?;
+    parse_response(&String::from_utf8_lossy(&raw))
+        .ok_or_else(|| AocError::bad("bad response from", url))
+}
+
+fn parse_response(raw: &str) -> Option<Response> {
+    let (head, body) = raw.split_once("\r\n\r\n")?;
+    let status = head
+        .lines()
+        .next()?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()?;
+    Some(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+/// Quote a value for a curl config file.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn curl(method: &str, url: &str, headers: &[(&str, &str)], body: Option<&str>) -> Result<Response> {
+    let mut config = format!("url = {}\nrequest = {}\n", quote(url), quote(method));
+    for (name, value) in headers {
+        config.push_str(&format!(
+            "header = {}\n",
+            quote(&format!("{}: {}", name, value))
+        ));
+    }
+    if let Some(body) = body {
+        config.push_str(&format!("data-raw = {}\n", quote(body)));
+    }
+
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--config", "-"])
+        .args(["--max-time", &TIMEOUT.as_secs().to_string()])
+        .args(["--write-out", "\n%{http_code}"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| AocError::new(format!("cannot run curl for {}: {}", url, err)))?;
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(config.as_bytes())
+        .map_err(io_error(url))?;
+    let output = child.wait_with_output().map_err(io_error(url))?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr);
+        return Err(AocError::new(format!("{}: {}", url, message.trim())));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or_else(|| AocError::bad("bad response from", url))?;
+    Ok(Response {
+        status: status
+            .trim()
+            .parse()
+            .map_err(|_| AocError::bad("bad response from", url))?,
+        body: body.to_string(),
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::{net::TcpListener, thread};
+
+    /// Serve one canned response for each request, on a local port.
+    /// Returns the base URL of the server and a handle that yields the
+    /// requests received once all responses are served.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = vec![];
+                let mut buf = [0; 4096];
+                // Read the head, and then the body if there is one.
+                loop {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| line.strip_prefix("Content-Length: "))
+                            .map_or(0, |n| n.parse().unwrap());
+                        if body.len() >= length {
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                requests.push(String::from_utf8_lossy(&request).to_string());
+                let response = format!(
+                    "HTTP/1.0 {} X\r\nContent-Type: text/plain\r\n\r\n{}",
+                    status, body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_parse_url() {
+        assert_eq!(
+            parse_url("http://localhost:8080/2020/day/1/input"),
+            Ok(Url {
+                host: "localhost",
+                port: 8080,
+                path: "/2020/day/1/input"
+            })
+        );
+        assert_eq!(
+            parse_url("http://example.com"),
+            Ok(Url {
+                host: "example.com",
+                port: 80,
+                path: "/"
+            })
+        );
+        assert_eq!(
+            parse_url("http://localhost:8080/").unwrap().host_header(),
+            "localhost:8080"
+        );
+        assert_eq!(
+            parse_url("http://example.com:80/").unwrap().host_header(),
+            "example.com"
+        );
+        assert!(parse_url("ftp://example.com/").is_err());
+    }
+
+    #[test]
+    fn test_request() {
+        let (url, server) = serve(vec![(200, "1\n2\n"), (404, "Not Found")]);
+        let response = get(&format!("{}/a", url), &[("Cookie", "session=abc")]).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "1\n2\n");
+        let response = post(&format!("{}/b", url), &[], "level=1&answer=2").unwrap();
+        assert!(!response.is_success());
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /a HTTP/1.0\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=abc\r\n"));
+        assert!(requests[1].starts_with("POST /b HTTP/1.0\r\n"));
+        assert!(requests[1].ends_with("\r\n\r\nlevel=1&answer=2"));
+    }
+}
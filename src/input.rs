@@ -0,0 +1,382 @@
+//! Locating and reading puzzle input.
+//!
+//! Input can be given explicitly as a path, read from standard input,
+//! or searched for. When searching, the directory in the environment
+//! variable `AOC_INPUT_DIR` is tried first, and then the directory
+//! where the inputs are kept in the source tree.
+//!
+//! The puzzle input for day N is in `aocN.dat` and the examples from
+//! the puzzle description are in `aocNex.dat`, `aocNex2.dat`,
+//! `aocNex3.dat`, and so on. The files of the first year are directly
+//! in the directories searched, and those of later years are in a
+//! subdirectory named after the year, like `2021/aoc1.dat` (see
+//! [`year_dir`]).
+//!
+//! Input is read as is and [normalized](normalize) only when it is
+//! parsed (see [`Solver`](crate::Solver)), so that the days do not have
+//! to care about how the file was saved.
+
+use crate::{registry::FIRST_YEAR, trace};
+use std::{
+    env, error, fmt,
+    fs::{read_dir, read_to_string},
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable with a directory to search for input in.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Puzzle input together with where it came from.
+#[derive(Debug)]
+pub struct Input {
+    pub text: String,
+    pub origin: String,
+}
+
+/// Where to read the input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    Search,
+    Example(u32),
+}
+
+impl Source {
+    /// Source for an optional command-line argument, where `-` means
+    /// standard input and no argument means searching for it.
+    pub fn from_arg(arg: Option<&str>) -> Source {
+        match arg {
+            Some("-") => Source::Stdin,
+            Some(path) => Source::Path(PathBuf::from(path)),
+            None => Source::Search,
+        }
+    }
+
+    /// Source given by command-line arguments of the form
+    /// `[--example [N]] [input]`.
+    pub fn from_args(args: &[String]) -> Result<Source, String> {
+        let mut source = None;
+        let mut iter = args.iter().peekable();
+        while let Some(arg) = iter.next() {
+            let next = match arg.as_str() {
+                "--example" => match iter.peek().and_then(|arg| arg.parse().ok()) {
+                    Some(number) => {
+                        iter.next();
+                        Source::Example(number)
+                    }
+                    None => Source::Example(1),
+                },
+                _ if arg.starts_with('-') && arg != "-" => {
+                    return Err(format!("unknown option '{}'", arg))
+                }
+                _ => Source::from_arg(Some(arg)),
+            };
+            if source.replace(next).is_some() {
+                return Err("more than one input given".to_string());
+            }
+        }
+        Ok(source.unwrap_or(Source::Search))
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Read(PathBuf, io::Error),
+    Stdin(io::Error),
+    NotFound(String, Vec<PathBuf>),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Read(path, err) => write!(f, "{}: {}", path.display(), err),
+            InputError::Stdin(err) => write!(f, "<stdin>: {}", err),
+            InputError::NotFound(name, tried) => {
+                let tried: Vec<String> = tried
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                write!(f, "{} not found, tried: {}", name, tried.join(", "))
+            }
+        }
+    }
+}
+
+impl error::Error for InputError {}
+
+/// Name of the input file for a day.
+pub fn file_name(day: u32) -> String {
+    format!("aoc{}.dat", day)
+}
+
+/// Name of the file with an example for a day. The first example has
+/// no number.
+pub fn example_file_name(day: u32, number: u32) -> String {
+    match number {
+        1 => format!("aoc{}ex.dat", day),
+        _ => format!("aoc{}ex{}.dat", day, number),
+    }
+}
+
+/// Day and example number of an example file name, if it is one.
+pub fn parse_example_file_name(name: &str) -> Option<(u32, u32)> {
+    let rest = name.strip_prefix("aoc")?.strip_suffix(".dat")?;
+    let (day, number) = rest.split_once("ex")?;
+    let day = day.parse().ok()?;
+    match number {
+        "" => Some((day, 1)),
+        _ => number
+            .parse()
+            .ok()
+            .filter(|&n| n > 1)
+            .map(|number| (day, number)),
+    }
+}
+
+/// Example file found in a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: u32,
+    pub number: u32,
+    pub path: PathBuf,
+}
+
+/// Find all example files in a directory, ordered by day and example
+/// number.
+pub fn find_examples(dir: &Path) -> io::Result<Vec<Example>> {
+    let mut examples = vec![];
+    for entry in read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|name| name.to_str());
+        if let Some((day, number)) = name.and_then(parse_example_file_name) {
+            examples.push(Example { day, number, path });
+        }
+    }
+    examples.sort_by_key(|example| (example.day, example.number));
+    Ok(examples)
+}
+
+/// Directory where the inputs are kept in the source tree.
+pub fn default_dir() -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("src/bin");
+    path
+}
+
+/// Directory with the files of a year below `dir`: `dir` itself for
+/// the first year, whose files have always been kept there, and the
+/// subdirectory named after the year for later years.
+pub fn year_dir(dir: &Path, year: u32) -> PathBuf {
+    match year {
+        FIRST_YEAR => dir.to_path_buf(),
+        _ => dir.join(year.to_string()),
+    }
+}
+
+/// Resolves the input for a day given the directories to search.
+#[derive(Debug, Clone)]
+pub struct Resolver {
+    pub input_dir: Option<PathBuf>,
+    pub default_dir: PathBuf,
+}
+
+impl Resolver {
+    /// Resolver using `AOC_INPUT_DIR`, if set, and the default
+    /// directory.
+    pub fn from_env() -> Resolver {
+        Resolver {
+            input_dir: env::var_os(INPUT_DIR_VAR).map(PathBuf::from),
+            default_dir: default_dir(),
+        }
+    }
+
+    /// Directories searched for the files of a year, in order.
+    pub fn dirs(&self, year: u32) -> Vec<PathBuf> {
+        self.input_dir
+            .iter()
+            .chain(Some(&self.default_dir))
+            .map(|dir| year_dir(dir, year))
+            .collect()
+    }
+
+    /// Locations searched for a file of a year, in order.
+    pub fn candidates(&self, year: u32, name: &str) -> Vec<PathBuf> {
+        self.dirs(year).iter().map(|dir| dir.join(name)).collect()
+    }
+
+    /// Directory where new input of a year is stored: below
+    /// `AOC_INPUT_DIR`, if set, and otherwise below the default
+    /// directory.
+    pub fn store_dir(&self, year: u32) -> PathBuf {
+        year_dir(self.input_dir.as_deref().unwrap_or(&self.default_dir), year)
+    }
+
+    fn search(&self, year: u32, name: &str) -> Result<Input, InputError> {
+        let candidates = self.candidates(year, name);
+        match candidates.iter().find(|path| path.is_file()) {
+            Some(path) => read_path(path.clone()),
+            None => Err(InputError::NotFound(name.to_string(), candidates)),
+        }
+    }
+
+    /// Read the input of a day of a year from the source.
+    pub fn resolve(&self, year: u32, day: u32, source: &Source) -> Result<Input, InputError> {
+        let input = match source {
+            Source::Path(path) => read_path(path.clone()),
+            Source::Stdin => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(InputError::Stdin)?;
+                Ok(Input {
+                    text,
+                    origin: "<stdin>".to_string(),
+                })
+            }
+            Source::Search => self.search(year, &file_name(day)),
+            Source::Example(number) => self.search(year, &example_file_name(day, *number)),
+        }?;
+        Ok(input)
+    }
+}
+
+/// Normalize the line endings and whitespace of input text. CRLF line
+/// endings become LF, trailing whitespace is removed from each line,
+/// and blank lines at the end are dropped, so that non-empty text ends
+/// with exactly one newline. Leading whitespace is kept, as are blank
+/// lines before the end, so line and column numbers stay the same.
+pub fn normalize(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + 1);
+    for line in text.lines() {
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+    let len = result.trim_end_matches('\n').len();
+    result.truncate(len);
+    if !result.is_empty() {
+        result.push('\n');
+    }
+    result
+}
+
+fn read_path(path: PathBuf) -> Result<Input, InputError> {
+    match read_to_string(&path) {
+        Ok(text) => Ok(Input {
+            text,
+            origin: path.display().to_string(),
+        }),
+        Err(err) => Err(InputError::Read(path, err)),
+    }
+}
+
+/// Read the input of a day of a year from the source, searching the
+/// default locations if needed.
+pub fn load(year: u32, day: u32, source: &Source) -> Result<Input, InputError> {
+    Resolver::from_env().resolve(year, day, source)
+}
+
+/// Read the input of a day given by the command-line arguments, as
+/// described in [`Source::from_args`], after setting the verbosity
+/// given with `-v` or `-vv`.
+pub fn from_args(year: u32, day: u32) -> Result<Input, Box<dyn error::Error>> {
+    let args = trace::take_verbosity(env::args().skip(1).collect());
+    let source = Source::from_args(&args)?;
+    Ok(load(year, day, &source)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidates() {
+        let resolver = Resolver {
+            input_dir: Some(PathBuf::from("/inputs")),
+            default_dir: PathBuf::from("/src"),
+        };
+        assert_eq!(
+            resolver.candidates(2020, "aoc7.dat"),
+            vec![
+                PathBuf::from("/inputs/aoc7.dat"),
+                PathBuf::from("/src/aoc7.dat")
+            ]
+        );
+        assert_eq!(
+            resolver.candidates(2021, "aoc7.dat"),
+            vec![
+                PathBuf::from("/inputs/2021/aoc7.dat"),
+                PathBuf::from("/src/2021/aoc7.dat")
+            ]
+        );
+        assert_eq!(resolver.store_dir(2021), PathBuf::from("/inputs/2021"));
+    }
+
+    #[test]
+    fn test_search() {
+        let resolver = Resolver {
+            input_dir: Some(PathBuf::from("/nonexistent")),
+            default_dir: default_dir(),
+        };
+        let input = resolver.resolve(2020, 1, &Source::Search).unwrap();
+        assert!(input.origin.ends_with("aoc1.dat"));
+
+        let err = resolver.resolve(2020, 42, &Source::Search).unwrap_err();
+        // One line, so that it fits in the table of `aoc run`.
+        let message = err.to_string();
+        assert!(message.starts_with("aoc42.dat not found, tried: "));
+        assert_eq!(message.lines().count(), 1);
+        match err {
+            InputError::NotFound(name, tried) => {
+                assert_eq!(name, "aoc42.dat");
+                assert_eq!(tried.len(), 2);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let input = resolver.resolve(2020, 3, &Source::Example(1)).unwrap();
+        assert!(input.origin.ends_with("aoc3ex.dat"));
+    }
+
+    #[test]
+    fn test_example_file_name() {
+        assert_eq!(example_file_name(19, 1), "aoc19ex.dat");
+        assert_eq!(example_file_name(19, 2), "aoc19ex2.dat");
+        assert_eq!(parse_example_file_name("aoc19ex.dat"), Some((19, 1)));
+        assert_eq!(parse_example_file_name("aoc19ex2.dat"), Some((19, 2)));
+        assert_eq!(parse_example_file_name("aoc19ex1.dat"), None);
+        assert_eq!(parse_example_file_name("aoc19.dat"), None);
+        assert_eq!(parse_example_file_name("aoc19ex.expected"), None);
+    }
+
+    #[test]
+    fn test_from_args() {
+        let args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+        assert_eq!(Source::from_args(&args(&[])), Ok(Source::Search));
+        assert_eq!(Source::from_args(&args(&["-"])), Ok(Source::Stdin));
+        assert_eq!(
+            Source::from_args(&args(&["aoc1.dat"])),
+            Ok(Source::Path(PathBuf::from("aoc1.dat")))
+        );
+        assert_eq!(
+            Source::from_args(&args(&["--example"])),
+            Ok(Source::Example(1))
+        );
+        assert_eq!(
+            Source::from_args(&args(&["--example", "2"])),
+            Ok(Source::Example(2))
+        );
+        assert!(Source::from_args(&args(&["--example", "aoc1.dat"])).is_err());
+        assert!(Source::from_args(&args(&["--bogus"])).is_err());
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("a\r\n\r\n  b \r\nc\t"), "a\n\n  b\nc\n");
+        assert_eq!(normalize("a\n\n\n"), "a\n");
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+        assert_eq!(normalize("\r\n"), "");
+        assert_eq!(normalize(""), "");
+    }
+}
@@ -0,0 +1,424 @@
+//! Minimal JSON values for the files and output of the tools.
+//!
+//! Only what the tools need is supported: values can be built,
+//! written either compact or indented, and parsed back. Object keys
+//! keep their order so that written files are stable. Integers are
+//! kept apart from other numbers so that large answers survive a
+//! round trip.
+
+use crate::error::{AocError, Result};
+use std::fmt::{self, Write};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Build an object from key-value pairs.
+    pub fn object<K: Into<String>>(pairs: Vec<(K, Value)>) -> Value {
+        Value::Object(pairs.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// Look up a key in an object.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(pairs) => pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::Int(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Int(n) => Some(n as f64),
+            Value::Float(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Write the value indented by two spaces per level, with one
+    /// array element or object member per line.
+    pub fn pretty(&self) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, 0);
+        out
+    }
+
+    fn write_pretty(&self, out: &mut String, level: usize) {
+        let indent = |out: &mut String, level| out.push_str(&"  ".repeat(level));
+        match self {
+            Value::Array(values) if !values.is_empty() => {
+                out.push_str("[\n");
+                for (i, value) in values.iter().enumerate() {
+                    indent(out, level + 1);
+                    value.write_pretty(out, level + 1);
+                    out.push_str(if i + 1 < values.len() { ",\n" } else { "\n" });
+                }
+                indent(out, level);
+                out.push(']');
+            }
+            Value::Object(pairs) if !pairs.is_empty() => {
+                out.push_str("{\n");
+                for (i, (key, value)) in pairs.iter().enumerate() {
+                    indent(out, level + 1);
+                    write!(out, "{}: ", Value::String(key.clone())).unwrap();
+                    value.write_pretty(out, level + 1);
+                    out.push_str(if i + 1 < pairs.len() { ",\n" } else { "\n" });
+                }
+                indent(out, level);
+                out.push('}');
+            }
+            _ => write!(out, "{}", self).unwrap(),
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Value {
+        Value::Bool(b)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(n: i64) -> Value {
+        Value::Int(n)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(n: u32) -> Value {
+        Value::Int(n.into())
+    }
+}
+
+impl From<f64> for Value {
+    fn from(x: f64) -> Value {
+        Value::Float(x)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Value {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Value {
+        Value::String(s)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Value {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+impl fmt::Display for Value {
+    /// Write the value in compact form.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Float(x) if x.is_finite() => write!(f, "{:?}", x),
+            Value::Float(_) => write!(f, "null"),
+            Value::String(s) => {
+                f.write_char('"')?;
+                for ch in s.chars() {
+                    match ch {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        ch if (ch as u32) < 0x20 => write!(f, "\\u{:04x}", ch as u32)?,
+                        ch => f.write_char(ch)?,
+                    }
+                }
+                f.write_char('"')
+            }
+            Value::Array(values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_char(']')
+            }
+            Value::Object(pairs) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in pairs.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}:{}", Value::String(key.clone()), value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+/// Parse a JSON text into a value.
+pub fn parse(text: &str) -> Result<Value> {
+    let mut parser = Parser { text, pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos < text.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    /// Error at the current position.
+    fn error(&self, message: &str) -> AocError {
+        let before = &self.text[..self.pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        AocError::new(message).at_line(line).at_column(column)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(ch) = self.peek() {
+            if !ch.is_ascii_whitespace() {
+                break;
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, ch: char) -> Result<()> {
+        self.skip_whitespace();
+        if self.peek() == Some(ch) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", ch)))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(Value::String),
+            Some('-') | Some('0'..='9') => self.number(),
+            Some(_) => self.keyword(),
+            None => Err(self.error("unexpected end of text")),
+        }
+    }
+
+    fn keyword(&mut self) -> Result<Value> {
+        for (word, value) in &[
+            ("null", Value::Null),
+            ("true", Value::Bool(true)),
+            ("false", Value::Bool(false)),
+        ] {
+            if self.rest().starts_with(word) {
+                self.pos += word.len();
+                return Ok(value.clone());
+            }
+        }
+        Err(self.error("expected a value"))
+    }
+
+    fn number(&mut self) -> Result<Value> {
+        let start = self.pos;
+        while let Some(ch) = self.peek() {
+            if !(ch.is_ascii_digit() || "+-.eE".contains(ch)) {
+                break;
+            }
+            self.pos += 1;
+        }
+        let literal = &self.text[start..self.pos];
+        let value = if literal.contains(|ch| ".eE".contains(ch)) {
+            literal.parse().map(Value::Float).ok()
+        } else {
+            literal.parse().map(Value::Int).ok()
+        };
+        value.ok_or_else(|| {
+            self.pos = start;
+            self.error("invalid number")
+        })
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect('"')?;
+        let mut result = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(result),
+                Some('\\') => {
+                    let ch = match self.next() {
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => {
+                            let hex = self.rest().get(..4).unwrap_or("");
+                            let code = u32::from_str_radix(hex, 16)
+                                .map_err(|_| self.error("invalid escape"))?;
+                            self.pos += 4;
+                            std::char::from_u32(code).unwrap_or('\u{fffd}')
+                        }
+                        Some(ch @ '"') | Some(ch @ '\\') | Some(ch @ '/') => ch,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    result.push(ch);
+                }
+                Some(ch) => result.push(ch),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value> {
+        self.expect('[')?;
+        let mut values = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Value::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(Value::Array(values)),
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value> {
+        self.expect('{')?;
+        let mut pairs = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Value::Object(pairs));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(':')?;
+            pairs.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(Value::Object(pairs)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write() {
+        let value = Value::object(vec![
+            ("day", Value::Int(13)),
+            ("answer", Value::Int(535296695251210)),
+            ("error", Value::Null),
+            ("text", Value::from("a \"b\"\n")),
+            (
+                "list",
+                Value::Array(vec![Value::from(true), Value::from(1.5)]),
+            ),
+        ]);
+        assert_eq!(
+            value.to_string(),
+            r#"{"day":13,"answer":535296695251210,"error":null,"text":"a \"b\"\n","list":[true,1.5]}"#
+        );
+        assert_eq!(
+            Value::object(vec![("a", Value::Array(vec![Value::Int(1)]))]).pretty(),
+            "{\n  \"a\": [\n    1\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let value = Value::object(vec![
+            ("n", Value::Int(-12)),
+            ("x", Value::from(2.5e3)),
+            ("s", Value::from("tab\there \u{e9}")),
+            ("empty", Value::Array(vec![])),
+            ("nested", Value::object(vec![("ok", Value::from(false))])),
+        ]);
+        assert_eq!(parse(&value.to_string()), Ok(value.clone()));
+        assert_eq!(parse(&value.pretty()), Ok(value));
+        assert_eq!(parse(r#""A""#), Ok(Value::from("A")));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse("{\n  \"a\": 1,\n  \"b\" 2\n}").unwrap_err();
+        assert_eq!(err.to_string(), "3:7: expected ':'");
+        assert!(parse("[1, 2").is_err());
+        assert!(parse("1 2").is_err());
+    }
+}
@@ -1 +1,56 @@
+//! Solutions to [Advent of Code](https://adventofcode.com), starting
+//! with 2020.
+//!
+//! Each day is a module with a type implementing [`Solution`]. The
+//! days of 2020 are at the top of the crate, and those of later years
+//! in a module per year. All implemented days are listed in the
+//! [`registry`] by year and day, which allows any day to be solved
+//! without knowing its concrete types.
 
+pub mod bench;
+pub mod cache;
+pub mod check;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod docs;
+pub mod error;
+pub mod expected;
+pub mod fetch;
+pub mod generate;
+pub mod http;
+pub mod input;
+pub mod json;
+pub mod parse;
+pub mod random;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod serve;
+pub mod site;
+mod solution;
+pub mod submit;
+pub mod trace;
+pub mod verify;
+pub mod watch;
+
+pub use error::AocError;
+pub use solution::{Answer, Part, Solution, Solver};
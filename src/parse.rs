@@ -0,0 +1,176 @@
+//! Helpers for the common shapes of puzzle input.
+//!
+//! Most input is lines of values, paragraphs of lines separated by
+//! blank lines, or comma-separated values. The helpers parse these
+//! with `FromStr` and report errors with their location, as described
+//! in the `error` module: a value that fails to parse gets its column
+//! in the line, unless its own parser already set one, and the line
+//! number is set by the helper iterating over the lines.
+
+use crate::error::{AocError, Result};
+use std::str::FromStr;
+
+/// Parse a token, which has to be a slice of `line`. Errors get the
+/// token as text, unless they already have a text, and the column
+/// where the token starts.
+pub fn parse_token<T>(line: &str, token: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Into<AocError>,
+{
+    token.parse().map_err(|err: T::Err| {
+        let err = err.into();
+        let err = match err.text {
+            Some(_) => err,
+            None => err.with_text(token),
+        };
+        err.at_token(line, token)
+    })
+}
+
+/// Parse each line of the text, ignoring surrounding whitespace.
+pub fn parse_lines<T>(text: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<AocError>,
+{
+    parse_lines_with(text, |line| parse_token(line, line.trim()))
+}
+
+/// Parse each line of the text with `parse`, which only needs to set
+/// the column of errors.
+pub fn parse_lines_with<T, F>(text: &str, mut parse: F) -> Result<Vec<T>>
+where
+    F: FnMut(&str) -> Result<T>,
+{
+    text.lines()
+        .enumerate()
+        .map(|(lineno, line)| parse(line).map_err(|err| err.at_line(lineno + 1)))
+        .collect()
+}
+
+/// Parse the comma-separated values of a line, ignoring whitespace
+/// around the values.
+pub fn comma_separated<T>(line: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<AocError>,
+{
+    line.split(',')
+        .map(|value| parse_token(line, value.trim()))
+        .collect()
+}
+
+/// Lines of a paragraph, with the line number of the first line in
+/// the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paragraph<'a> {
+    pub first_line: usize,
+    pub lines: Vec<&'a str>,
+}
+
+impl<'a> Paragraph<'a> {
+    /// The paragraph without its first line, for paragraphs that start
+    /// with a heading.
+    pub fn body(&self) -> Paragraph<'a> {
+        Paragraph {
+            first_line: self.first_line + 1,
+            lines: self.lines[1..].to_vec(),
+        }
+    }
+
+    /// Parse each line of the paragraph, ignoring surrounding
+    /// whitespace.
+    pub fn parse_lines<T>(&self) -> Result<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Into<AocError>,
+    {
+        self.parse_lines_with(|line| parse_token(line, line.trim()))
+    }
+
+    /// Parse each line of the paragraph with `parse`, which only needs
+    /// to set the column of errors.
+    pub fn parse_lines_with<T, F>(&self, mut parse: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&str) -> Result<T>,
+    {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(offset, line)| parse(line).map_err(|err| err.at_line(self.first_line + offset)))
+            .collect()
+    }
+}
+
+/// Split the text into paragraphs, which are separated by one or more
+/// blank lines.
+pub fn paragraphs(text: &str) -> Vec<Paragraph<'_>> {
+    let mut paragraphs = vec![];
+    let mut current: Option<Paragraph> = None;
+    for (lineno, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            paragraphs.extend(current.take());
+        } else {
+            current
+                .get_or_insert_with(|| Paragraph {
+                    first_line: lineno + 1,
+                    lines: vec![],
+                })
+                .lines
+                .push(line);
+        }
+    }
+    paragraphs.extend(current);
+    paragraphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(parse_lines::<u32>("1\n 22 \n333\n"), Ok(vec![1, 22, 333]));
+        let err = parse_lines::<u32>("1\n 2x\n").unwrap_err();
+        assert_eq!(err.to_string(), "2:2: invalid number '2x'");
+        let words = parse_lines_with("ab\ncd", |line| Ok(line.len())).unwrap();
+        assert_eq!(words, vec![2, 2]);
+    }
+
+    #[test]
+    fn test_comma_separated() {
+        assert_eq!(comma_separated::<i32>("3, -1,4"), Ok(vec![3, -1, 4]));
+        let err = comma_separated::<i32>("3,1,x").unwrap_err();
+        assert_eq!(err.column, Some(5));
+        assert_eq!(err.to_string(), "invalid number 'x'");
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let text = "a\nb\n\n\nc\n  \nd\n";
+        let found = paragraphs(text);
+        assert_eq!(
+            found,
+            vec![
+                Paragraph {
+                    first_line: 1,
+                    lines: vec!["a", "b"]
+                },
+                Paragraph {
+                    first_line: 5,
+                    lines: vec!["c"]
+                },
+                Paragraph {
+                    first_line: 7,
+                    lines: vec!["d"]
+                },
+            ]
+        );
+        let err = paragraphs("1\n\nheading:\n4\nx\n")[1]
+            .body()
+            .parse_lines::<u32>()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "5:1: invalid number 'x'");
+    }
+}
@@ -0,0 +1,191 @@
+//! Registry of all implemented days, of all years.
+//!
+//! Days are identified by their year and number. The days of the
+//! first year, 2020, are the `dayNN` modules at the top of the crate,
+//! where they have always been, while the days of later years are in
+//! a module per year, like `y2021::day01` in `src/y2021/day01.rs`.
+
+use crate::{
+    day01::Day01, day02::Day02, day03::Day03, day04::Day04, day05::Day05, day06::Day06,
+    day07::Day07, day08::Day08, day09::Day09, day10::Day10, day11::Day11, day12::Day12,
+    day13::Day13, day14::Day14, day15::Day15, day16::Day16, day17::Day17, day18::Day18,
+    day19::Day19, Solver,
+};
+use std::path::PathBuf;
+
+/// The first year, whose days are at the top of the crate.
+pub const FIRST_YEAR: u32 = 2020;
+
+/// Entry in the registry.
+pub struct Day {
+    pub year: u32,
+    pub number: u32,
+    pub solver: &'static (dyn Solver + Sync),
+}
+
+static DAYS: [Day; 19] = [
+    Day {
+        year: 2020,
+        number: 1,
+        solver: &Day01,
+    },
+    Day {
+        year: 2020,
+        number: 2,
+        solver: &Day02,
+    },
+    Day {
+        year: 2020,
+        number: 3,
+        solver: &Day03,
+    },
+    Day {
+        year: 2020,
+        number: 4,
+        solver: &Day04,
+    },
+    Day {
+        year: 2020,
+        number: 5,
+        solver: &Day05,
+    },
+    Day {
+        year: 2020,
+        number: 6,
+        solver: &Day06,
+    },
+    Day {
+        year: 2020,
+        number: 7,
+        solver: &Day07,
+    },
+    Day {
+        year: 2020,
+        number: 8,
+        solver: &Day08,
+    },
+    Day {
+        year: 2020,
+        number: 9,
+        solver: &Day09,
+    },
+    Day {
+        year: 2020,
+        number: 10,
+        solver: &Day10,
+    },
+    Day {
+        year: 2020,
+        number: 11,
+        solver: &Day11,
+    },
+    Day {
+        year: 2020,
+        number: 12,
+        solver: &Day12,
+    },
+    Day {
+        year: 2020,
+        number: 13,
+        solver: &Day13,
+    },
+    Day {
+        year: 2020,
+        number: 14,
+        solver: &Day14,
+    },
+    Day {
+        year: 2020,
+        number: 15,
+        solver: &Day15,
+    },
+    Day {
+        year: 2020,
+        number: 16,
+        solver: &Day16,
+    },
+    Day {
+        year: 2020,
+        number: 17,
+        solver: &Day17,
+    },
+    Day {
+        year: 2020,
+        number: 18,
+        solver: &Day18,
+    },
+    Day {
+        year: 2020,
+        number: 19,
+        solver: &Day19,
+    },
+];
+
+/// All implemented days, ordered by year and number.
+pub fn days() -> &'static [Day] {
+    &DAYS
+}
+
+/// The implemented days of a year, in order.
+pub fn days_of(year: u32) -> impl Iterator<Item = &'static Day> {
+    DAYS.iter().filter(move |day| day.year == year)
+}
+
+/// The years with implemented days, in order.
+pub fn years() -> Vec<u32> {
+    let mut years: Vec<u32> = DAYS.iter().map(|day| day.year).collect();
+    years.dedup();
+    years
+}
+
+/// The latest year with implemented days, which is the year used
+/// unless another is asked for.
+pub fn latest_year() -> u32 {
+    DAYS.last().map_or(FIRST_YEAR, |day| day.year)
+}
+
+/// Look up a day by its year and number.
+pub fn find(year: u32, number: u32) -> Option<&'static Day> {
+    DAYS.iter()
+        .find(|day| day.year == year && day.number == number)
+}
+
+/// Path of the module of a day relative to the crate, like `day07`
+/// or `y2021::day07`.
+pub fn module_path(year: u32, number: u32) -> String {
+    match year {
+        FIRST_YEAR => format!("day{:02}", number),
+        _ => format!("y{}::day{:02}", year, number),
+    }
+}
+
+/// Source file of a day relative to the root of the source tree, like
+/// `src/day07.rs` or `src/y2021/day07.rs`.
+pub fn source_path(year: u32, number: u32) -> PathBuf {
+    PathBuf::from(format!(
+        "src/{}.rs",
+        module_path(year, number).replace("::", "/")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(2020, 7).map(|day| day.number), Some(7));
+        assert!(find(2020, 0).is_none());
+        assert!(find(2020, 26).is_none());
+        assert!(find(2019, 7).is_none());
+        assert_eq!(years()[0], FIRST_YEAR);
+        assert_eq!(days_of(2020).count(), 19);
+    }
+
+    #[test]
+    fn test_source_path() {
+        assert_eq!(source_path(2020, 7), PathBuf::from("src/day07.rs"));
+        assert_eq!(source_path(2021, 12), PathBuf::from("src/y2021/day12.rs"));
+        assert_eq!(module_path(2021, 12), "y2021::day12");
+    }
+}
@@ -0,0 +1,507 @@
+//! Write-up of the days of a year as a static site.
+//!
+//! Each day gets a page with the puzzle text from its module
+//! documentation (see the `docs` module), the answers it gives now
+//! together with the recorded ones, the time it took, and a link to
+//! its source. An index page lists all days. The site is written as
+//! HTML, or as Markdown for viewing where Markdown is rendered.
+//!
+//! The documentation is Markdown, but only the parts used in the
+//! modules are converted to HTML: headings, paragraphs, lists, code
+//! blocks, inline code, emphasis, and links.
+
+use crate::{
+    docs,
+    error::{AocError, Result},
+    expected::Expected,
+    runner::{format_duration, Outcome},
+    Part,
+};
+use regex::Regex;
+use std::{
+    fs::{create_dir_all, write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Format of the site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Html,
+    Markdown,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Html => "html",
+            Format::Markdown => "md",
+        }
+    }
+}
+
+/// Everything shown for a day.
+#[derive(Debug)]
+pub struct Page {
+    pub day: u32,
+    /// Title from the first line of the documentation, like `Day 9:
+    /// Encoding Error`.
+    pub title: String,
+    /// The documentation without the title.
+    pub text: String,
+    pub recorded: Expected,
+    /// Outcomes of solving the parts, empty if the day was not run.
+    pub outcomes: Vec<Outcome>,
+    /// Link to the source of the day.
+    pub source: String,
+}
+
+impl Page {
+    /// Page for a day from the source of its module.
+    pub fn new(day: u32, module_source: &str, outcomes: Vec<Outcome>, source: String) -> Page {
+        let docs = docs::module_docs(module_source);
+        let (title, text) = docs.split_once('\n').unwrap_or((&docs, ""));
+        Page {
+            day,
+            title: title.trim().to_string(),
+            text: text.trim_start_matches('\n').to_string(),
+            recorded: docs::recorded_answers(&docs),
+            outcomes,
+            source,
+        }
+    }
+
+    fn file_name(&self, format: Format) -> String {
+        format!("day{:02}.{}", self.day, format.extension())
+    }
+
+    /// Answer to a part, or what went wrong.
+    fn answer(&self, part: Part) -> String {
+        match self.outcomes.iter().find(|outcome| outcome.part == part) {
+            Some(outcome) => match &outcome.result {
+                Ok(answer) => answer.to_string(),
+                Err(_) if outcome.timed_out => "timed out".to_string(),
+                Err(err) => format!("error: {}", err),
+            },
+            None => "not run".to_string(),
+        }
+    }
+
+    /// Whether the answer to a part is the recorded one, if one is
+    /// recorded and the part was solved.
+    fn is_recorded(&self, part: Part) -> Option<bool> {
+        let recorded = self.recorded.get(part)?;
+        let outcome = self.outcomes.iter().find(|outcome| outcome.part == part)?;
+        let answer = outcome.result.as_ref().ok()?;
+        Some(answer.to_string() == recorded)
+    }
+
+    fn duration(&self) -> Duration {
+        self.outcomes.iter().map(|outcome| outcome.duration).sum()
+    }
+
+    /// Rows of the answer table: part, answer, recorded answer, and
+    /// time.
+    fn answer_rows(&self) -> Vec<[String; 4]> {
+        [Part::One, Part::Two]
+            .iter()
+            .map(|&part| {
+                let duration = self
+                    .outcomes
+                    .iter()
+                    .find(|outcome| outcome.part == part)
+                    .map_or(String::new(), |outcome| format_duration(outcome.duration));
+                let recorded = match (self.recorded.get(part), self.is_recorded(part)) {
+                    (Some(recorded), Some(false)) => format!("{} (differs)", recorded),
+                    (Some(recorded), _) => recorded.to_string(),
+                    (None, _) => "-".to_string(),
+                };
+                [part.to_string(), self.answer(part), recorded, duration]
+            })
+            .collect()
+    }
+}
+
+const ANSWER_HEADER: [&str; 4] = ["Part", "Answer", "Recorded", "Time"];
+const INDEX_HEADER: [&str; 5] = ["Day", "Title", "Part 1", "Part 2", "Time"];
+
+/// Escape text for HTML.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Convert inline Markdown in escaped text: emphasis and links.
+fn inline_markup(text: &str) -> String {
+    let strong = Regex::new(r"\*\*([^*\s](?:[^*]*[^*\s])?)\*\*").unwrap();
+    let emphasis = Regex::new(r"\*([^*\s](?:[^*]*[^*\s])?)\*").unwrap();
+    let link = Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").unwrap();
+    let text = strong.replace_all(text, "<strong>$1</strong>");
+    let text = emphasis.replace_all(&text, "<em>$1</em>");
+    link.replace_all(&text, r#"<a href="$2">$1</a>"#)
+        .into_owned()
+}
+
+/// Convert a line of Markdown text to HTML, with inline code.
+fn inline(text: &str) -> String {
+    text.split('`')
+        .enumerate()
+        .map(|(index, part)| match index % 2 {
+            0 => inline_markup(&escape(part)),
+            _ => format!("<code>{}</code>", escape(part)),
+        })
+        .collect()
+}
+
+/// Convert Markdown to HTML. Headings are moved down by `shift`
+/// levels, so that they fit under the heading of the page.
+pub fn markdown_to_html(text: &str, shift: usize) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = vec![];
+    let mut list: Vec<String> = vec![];
+    let mut code: Option<Vec<&str>> = None;
+    let mut indented: Vec<&str> = vec![];
+
+    fn flush(html: &mut String, paragraph: &mut Vec<&str>, list: &mut Vec<String>) {
+        if !paragraph.is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", inline(&paragraph.join("\n"))));
+            paragraph.clear();
+        }
+        if !list.is_empty() {
+            html.push_str("<ul>\n");
+            for item in list.iter() {
+                html.push_str(&format!("<li>{}</li>\n", inline(item)));
+            }
+            html.push_str("</ul>\n");
+            list.clear();
+        }
+    }
+    fn flush_code(html: &mut String, lines: &[&str]) {
+        html.push_str(&format!(
+            "<pre><code>{}\n</code></pre>\n",
+            escape(&lines.join("\n"))
+        ));
+    }
+
+    for line in text.lines() {
+        if let Some(lines) = &mut code {
+            if line.trim_start().starts_with("```") {
+                flush_code(&mut html, lines);
+                code = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("    ") {
+            if paragraph.is_empty() && list.is_empty() {
+                indented.push(rest);
+                continue;
+            }
+        }
+        if !indented.is_empty() {
+            flush_code(&mut html, &indented);
+            indented.clear();
+        }
+        let trimmed = line.trim();
+        let level = trimmed.chars().take_while(|&ch| ch == '#').count();
+        if trimmed.starts_with("```") {
+            flush(&mut html, &mut paragraph, &mut list);
+            code = Some(vec![]);
+        } else if trimmed.is_empty() {
+            flush(&mut html, &mut paragraph, &mut list);
+        } else if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            flush(&mut html, &mut paragraph, &mut list);
+            let heading = inline(trimmed[level..].trim());
+            let level = (level + shift).min(6);
+            html.push_str(&format!("<h{}>{}</h{}>\n", level, heading, level));
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            if !paragraph.is_empty() {
+                flush(&mut html, &mut paragraph, &mut list);
+            }
+            list.push(item.to_string());
+        } else if let (Some(last), true) = (list.last_mut(), line.starts_with(' ')) {
+            // Continuation of a list item.
+            last.push('\n');
+            last.push_str(trimmed);
+        } else {
+            if !list.is_empty() {
+                flush(&mut html, &mut paragraph, &mut list);
+            }
+            paragraph.push(trimmed);
+        }
+    }
+    if let Some(lines) = &code {
+        flush_code(&mut html, lines);
+    }
+    if !indented.is_empty() {
+        flush_code(&mut html, &indented);
+    }
+    flush(&mut html, &mut paragraph, &mut list);
+    html
+}
+
+/// Move the headings of Markdown text down by `shift` levels, like
+/// `markdown_to_html` does, leaving lines in code blocks alone.
+fn shift_headings(text: &str, shift: usize) -> String {
+    let mut lines = vec![];
+    let mut fenced = false;
+    // Whether a paragraph or list is open, in which an indented line
+    // is not code.
+    let mut in_text = false;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if fenced {
+            fenced = !trimmed.starts_with("```");
+            lines.push(line.to_string());
+            continue;
+        }
+        if line.starts_with("    ") && !in_text {
+            lines.push(line.to_string());
+            continue;
+        }
+        let level = trimmed.chars().take_while(|&ch| ch == '#').count();
+        if trimmed.starts_with("```") {
+            fenced = true;
+            in_text = false;
+        } else if trimmed.is_empty() {
+            in_text = false;
+        } else if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            in_text = false;
+            let shifted = (level + shift).min(6);
+            lines.push(format!("{}{}", "#".repeat(shifted), &trimmed[level..]));
+            continue;
+        } else {
+            in_text = true;
+        }
+        lines.push(line.to_string());
+    }
+    lines.join("\n")
+}
+
+fn html_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> String {
+    let cells = |tag: &str, row: &[String]| -> String {
+        row.iter()
+            .map(|cell| format!("<{}>{}</{}>", tag, cell, tag))
+            .collect()
+    };
+    let header: Vec<String> = header.iter().map(|cell| cell.to_string()).collect();
+    let mut html = format!("<table>\n<tr>{}</tr>\n", cells("th", &header));
+    for row in rows {
+        html.push_str(&format!("<tr>{}</tr>\n", cells("td", row)));
+    }
+    html + "</table>\n"
+}
+
+fn markdown_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> String {
+    // A `|` in a cell would end it.
+    let row = |cells: Vec<String>| format!("| {} |\n", cells.join(" | ").replace('\n', " "));
+    let mut markdown = row(header.iter().map(|cell| cell.to_string()).collect());
+    markdown.push_str(&row(header.iter().map(|_| "---".to_string()).collect()));
+    for cells in rows {
+        let cells = cells.iter().map(|cell| cell.replace('|', "\\|")).collect();
+        markdown.push_str(&row(cells));
+    }
+    markdown
+}
+
+fn html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>\nbody {{ max-width: 50em; margin: auto; font-family: sans-serif; }}\n\
+         table {{ border-collapse: collapse; }}\n\
+         td, th {{ border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: left; }}\n\
+         pre {{ background: #f4f4f4; padding: 0.5em; }}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape(title),
+        body
+    )
+}
+
+/// The index page of a year, listing the days with their answers.
+pub fn index(year: u32, pages: &[Page], format: Format) -> String {
+    let rows: Vec<[String; 5]> = pages
+        .iter()
+        .map(|page| {
+            let title = match format {
+                Format::Html => format!(
+                    "<a href=\"{}\">{}</a>",
+                    page.file_name(format),
+                    escape(&page.title)
+                ),
+                Format::Markdown => format!("[{}]({})", page.title, page.file_name(format)),
+            };
+            let answer = |part| match format {
+                Format::Html => escape(&page.answer(part)),
+                Format::Markdown => page.answer(part),
+            };
+            [
+                page.day.to_string(),
+                title,
+                answer(Part::One),
+                answer(Part::Two),
+                match page.outcomes.is_empty() {
+                    true => String::new(),
+                    false => format_duration(page.duration()),
+                },
+            ]
+        })
+        .collect();
+    let title = format!("Advent of Code {}", year);
+    match format {
+        Format::Html => html_document(
+            &title,
+            &format!("<h1>{}</h1>\n{}", title, html_table(INDEX_HEADER, &rows)),
+        ),
+        Format::Markdown => format!("# {}\n\n{}", title, markdown_table(INDEX_HEADER, &rows)),
+    }
+}
+
+/// The page of a day.
+pub fn page(page: &Page, format: Format) -> String {
+    let rows = page.answer_rows();
+    match format {
+        Format::Html => {
+            let rows: Vec<[String; 4]> = rows
+                .iter()
+                .map(|row| {
+                    let mut escaped = row.clone();
+                    escaped.iter_mut().for_each(|cell| *cell = escape(cell));
+                    escaped
+                })
+                .collect();
+            let body = format!(
+                "<p><a href=\"index.html\">All days</a></p>\n<h1>{}</h1>\n{}\
+                 <p><a href=\"{}\">Source</a></p>\n{}",
+                escape(&page.title),
+                html_table(ANSWER_HEADER, &rows),
+                escape(&page.source),
+                markdown_to_html(&page.text, 1)
+            );
+            html_document(&page.title, &body)
+        }
+        Format::Markdown => {
+            format!(
+                "[All days](index.md)\n\n# {}\n\n{}\n[Source]({})\n\n{}\n",
+                page.title,
+                markdown_table(ANSWER_HEADER, &rows),
+                page.source,
+                shift_headings(&page.text, 1)
+            )
+        }
+    }
+}
+
+/// Write the site of a year into `dir`, returning the files written.
+pub fn write_site(dir: &Path, year: u32, pages: &[Page], format: Format) -> Result<Vec<PathBuf>> {
+    let io_error = |path: &Path, err| AocError::new(format!("{}: {}", path.display(), err));
+    create_dir_all(dir).map_err(|err| io_error(dir, err))?;
+    let mut files = vec![(
+        dir.join(format!("index.{}", format.extension())),
+        index(year, pages, format),
+    )];
+    for day in pages {
+        files.push((dir.join(day.file_name(format)), page(day, format)));
+    }
+    let mut written = vec![];
+    for (path, contents) in files {
+        write(&path, contents).map_err(|err| io_error(&path, err))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+
+    const SOURCE: &str = "//! Day 9: Encoding Error
+//!
+//! Find the *first* number, like `127`:
+//!
+//! ```text
+//! 35 < 40
+//! ```
+//!
+//! Your puzzle answer was 1398413738.
+//!
+//! # Part Two
+//!
+//! - one
+//! - two
+//!
+//! Your puzzle answer was 169521051.
+";
+
+    fn day9() -> Page {
+        let outcomes = vec![Outcome {
+            year: 2020,
+            day: 9,
+            part: Part::One,
+            result: Ok(Answer::Number(1398413738)),
+            duration: Duration::from_micros(250),
+            timed_out: false,
+            cached: false,
+        }];
+        Page::new(9, SOURCE, outcomes, "src/day09.rs".to_string())
+    }
+
+    #[test]
+    fn test_markdown_to_html() {
+        assert_eq!(
+            markdown_to_html(&day9().text, 1),
+            "<p>Find the <em>first</em> number, like <code>127</code>:</p>\n\
+             <pre><code>35 &lt; 40\n</code></pre>\n\
+             <p>Your puzzle answer was 1398413738.</p>\n\
+             <h2>Part Two</h2>\n\
+             <ul>\n<li>one</li>\n<li>two</li>\n</ul>\n\
+             <p>Your puzzle answer was 169521051.</p>\n"
+        );
+        assert_eq!(
+            markdown_to_html("1721 * 299 = 514579\n\n    indented\n", 0),
+            "<p>1721 * 299 = 514579</p>\n<pre><code>indented\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn test_page() {
+        let page = day9();
+        assert_eq!(page.title, "Day 9: Encoding Error");
+        let markdown = super::page(&page, Format::Markdown);
+        assert!(markdown.contains("\n| 1 | 1398413738 | 1398413738 | 250 µs |\n"));
+        assert!(markdown.contains("\n| 2 | not run | 169521051 |  |\n"));
+        assert!(markdown.contains("\n## Part Two\n"));
+        let html = super::page(&page, Format::Html);
+        assert!(html.contains("<h1>Day 9: Encoding Error</h1>"));
+        assert!(html.contains("<a href=\"src/day09.rs\">Source</a>"));
+
+        let index = index(2020, &[page], Format::Markdown);
+        assert!(index.starts_with("# Advent of Code 2020\n"));
+        assert!(index.contains("| 9 | [Day 9: Encoding Error](day09.md) | 1398413738 | not run |"));
+    }
+
+    #[test]
+    fn test_shift_headings() {
+        let text = "# Title\n\n```\n#[derive(Debug)]\n# not a heading\n```\n\n    # indented\n\n## Part Two\n#hashtag";
+        assert_eq!(
+            shift_headings(text, 1),
+            "## Title\n\n```\n#[derive(Debug)]\n# not a heading\n```\n\n    # indented\n\n### Part Two\n#hashtag"
+        );
+        let source = "//! Day 1: Title\n//!\n//! ```\n//! #[derive(Debug)]\n//! ```\n";
+        let page = Page::new(1, source, vec![], "src/day01.rs".to_string());
+        let markdown = super::page(&page, Format::Markdown);
+        assert!(markdown.contains("\n```\n#[derive(Debug)]\n```\n"));
+    }
+}
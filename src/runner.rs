@@ -0,0 +1,384 @@
+//! Running solvers and reporting their answers.
+//!
+//! Days can be run one at a time, or several at once on a pool of
+//! worker threads with a time limit for each day. A solver cannot be
+//! stopped from the outside, so a day that runs out of time is
+//! reported as timed out and left running in the background until the
+//! process exits.
+
+use crate::{error::Result, input::Input, json::Value, registry::Day, Answer, AocError, Part};
+use std::{
+    any::Any,
+    collections::VecDeque,
+    convert::TryFrom,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Outcome of solving one part of a day.
+#[derive(Debug)]
+pub struct Outcome {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    pub result: Result<Answer>,
+    pub duration: Duration,
+    /// The day ran out of time before this part was solved.
+    pub timed_out: bool,
+    /// The answer was taken from the cache, and the duration is that
+    /// of the run that put it there.
+    pub cached: bool,
+}
+
+impl Outcome {
+    /// Outcome of a part of a day that failed with an error.
+    pub fn failed(day: &Day, part: Part, err: AocError) -> Outcome {
+        Outcome {
+            year: day.year,
+            day: day.number,
+            part,
+            result: Err(err),
+            duration: Duration::default(),
+            timed_out: false,
+            cached: false,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        format!("panicked: {}", msg)
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        format!("panicked: {}", msg)
+    } else {
+        "panicked".to_string()
+    }
+}
+
+/// Call a solver function, turning a panic into an error so that one
+/// broken day does not take down the others.
+pub fn guard<T, F>(func: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(func)) {
+        Ok(result) => result,
+        Err(payload) => Err(AocError::new(panic_message(payload))),
+    }
+}
+
+/// Solve the given parts of a day, passing the outcome of each part
+/// to `report` as soon as it is known.
+///
+/// The text is parsed once and shared between the parts. If parsing
+/// fails, all parts fail with the parse error.
+pub fn run_with<F>(day: &Day, text: &str, parts: &[Part], mut report: F)
+where
+    F: FnMut(Outcome),
+{
+    let input = match guard(|| day.solver.parse_input(text)) {
+        Ok(input) => input,
+        Err(err) => {
+            for &part in parts {
+                report(Outcome::failed(day, part, err.clone()));
+            }
+            return;
+        }
+    };
+
+    for &part in parts {
+        let start = Instant::now();
+        let result = guard(|| day.solver.solve_part(input.as_ref(), part));
+        report(Outcome {
+            year: day.year,
+            day: day.number,
+            part,
+            result,
+            duration: start.elapsed(),
+            timed_out: false,
+            cached: false,
+        });
+    }
+}
+
+/// Solve the given parts of a day.
+pub fn run(day: &Day, text: &str, parts: &[Part]) -> Vec<Outcome> {
+    let mut outcomes = vec![];
+    run_with(day, text, parts, |outcome| outcomes.push(outcome));
+    outcomes
+}
+
+/// Report an error at a line of the input against the file it was
+/// read from.
+fn locate(mut outcome: Outcome, origin: &str) -> Outcome {
+    if let Err(err) = &mut outcome.result {
+        if err.line.is_some() {
+            *err = err.clone().in_file(origin);
+        }
+    }
+    outcome
+}
+
+/// Solve the given parts of a day for an input, reporting errors at a
+/// line of the input against the file it was read from.
+pub fn run_input(day: &Day, input: &Input, parts: &[Part]) -> Vec<Outcome> {
+    run(day, &input.text, parts)
+        .into_iter()
+        .map(|outcome| locate(outcome, &input.origin))
+        .collect()
+}
+
+/// Solve the given parts of a day for an input, giving up on the parts
+/// not solved within `limit`.
+fn run_limited(day: &'static Day, input: Input, parts: &[Part], limit: Duration) -> Vec<Outcome> {
+    let (sender, receiver) = mpsc::channel();
+    let solve_parts = parts.to_vec();
+    thread::spawn(move || {
+        run_with(day, &input.text, &solve_parts, |outcome| {
+            // The receiver is gone if the day timed out.
+            let _ = sender.send(locate(outcome, &input.origin));
+        })
+    });
+
+    let start = Instant::now();
+    let mut outcomes = vec![];
+    for &part in parts {
+        let left = limit.checked_sub(start.elapsed()).unwrap_or_default();
+        match receiver.recv_timeout(left) {
+            Ok(outcome) => outcomes.push(outcome),
+            Err(RecvTimeoutError::Timeout) => outcomes.push(Outcome {
+                duration: start.elapsed(),
+                timed_out: true,
+                ..Outcome::failed(
+                    day,
+                    part,
+                    AocError::new(format!("timed out after {}", format_duration(limit))),
+                )
+            }),
+            Err(RecvTimeoutError::Disconnected) => {
+                outcomes.push(Outcome::failed(day, part, AocError::new("solver stopped")))
+            }
+        }
+    }
+    outcomes
+}
+
+/// Solve the given parts of the days on a pool of `workers` threads,
+/// each day with its input. If there is a `limit`, the parts of a day
+/// not solved within it are reported as timed out. The outcomes are
+/// returned in the order of the days.
+pub fn run_parallel(
+    jobs: Vec<(&'static Day, Input)>,
+    parts: &[Part],
+    workers: usize,
+    limit: Option<Duration>,
+) -> Vec<Outcome> {
+    let count = jobs.len();
+    let queue = Arc::new(Mutex::new(
+        jobs.into_iter().enumerate().collect::<VecDeque<_>>(),
+    ));
+    let (sender, receiver) = mpsc::channel();
+    for _ in 0..workers.clamp(1, count.max(1)) {
+        let queue = Arc::clone(&queue);
+        let sender = sender.clone();
+        let parts = parts.to_vec();
+        thread::spawn(move || loop {
+            let job = queue.lock().unwrap().pop_front();
+            let (index, (day, input)) = match job {
+                Some(job) => job,
+                None => break,
+            };
+            let outcomes = match limit {
+                Some(limit) => run_limited(day, input, &parts, limit),
+                None => run_input(day, &input, &parts),
+            };
+            if sender.send((index, outcomes)).is_err() {
+                break;
+            }
+        });
+    }
+    drop(sender);
+
+    let mut results: Vec<Vec<Outcome>> = (0..count).map(|_| vec![]).collect();
+    for (index, outcomes) in receiver {
+        results[index] = outcomes;
+    }
+    results.into_iter().flatten().collect()
+}
+
+/// Format a duration with a unit suitable for its size.
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros == 0 {
+        format!("{} ns", duration.as_nanos())
+    } else if micros < 1000 {
+        format!("{} µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.1} ms", micros as f64 / 1000.0)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+/// Print the outcomes as a table with one row per part.
+pub fn print_table(outcomes: &[Outcome]) {
+    println!(
+        "{:>3}  {:>4}  {:<20}  {:>10}",
+        "Day", "Part", "Answer", "Time"
+    );
+    for outcome in outcomes {
+        let answer = match &outcome.result {
+            Ok(answer) => answer.to_string(),
+            Err(_) if outcome.timed_out => "timed out".to_string(),
+            Err(err) => format!("error: {}", err),
+        };
+        let time = if outcome.cached {
+            "cached".to_string()
+        } else {
+            format_duration(outcome.duration)
+        };
+        println!(
+            "{:>3}  {:>4}  {:<20}  {:>10}",
+            outcome.day, outcome.part, answer, time
+        );
+    }
+}
+
+/// Convert an outcome to JSON: an object holding the year, the day,
+/// the part, the answer (a number or a string, `null` on error), the
+/// time taken in nanoseconds, the status (`ok`, `error`, or
+/// `timeout`), the error message (`null` unless failed), and whether
+/// the answer came from the cache.
+pub fn outcome_json(outcome: &Outcome) -> Value {
+    let (answer, status, error) = match &outcome.result {
+        Ok(Answer::Number(n)) => (Value::Int(*n), "ok", Value::Null),
+        Ok(Answer::Text(s)) => (Value::from(s.as_str()), "ok", Value::Null),
+        Err(err) if outcome.timed_out => (Value::Null, "timeout", Value::from(err.to_string())),
+        Err(err) => (Value::Null, "error", Value::from(err.to_string())),
+    };
+    let nanos = i64::try_from(outcome.duration.as_nanos()).unwrap_or(i64::MAX);
+    Value::object(vec![
+        ("year", Value::from(outcome.year)),
+        ("day", Value::from(outcome.day)),
+        ("part", Value::from(outcome.part.number())),
+        ("answer", answer),
+        ("duration_ns", Value::Int(nanos)),
+        ("status", Value::from(status)),
+        ("error", error),
+        ("cached", Value::Bool(outcome.cached)),
+    ])
+}
+
+/// Convert the outcomes to JSON: an array with an object for each
+/// part, see `outcome_json`.
+pub fn to_json(outcomes: &[Outcome]) -> Value {
+    Value::Array(outcomes.iter().map(outcome_json).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_run() {
+        let day = registry::find(2020, 1).unwrap();
+        let outcomes = run(
+            day,
+            "1721\n979\n366\n299\n675\n1456\n",
+            &[Part::One, Part::Two],
+        );
+        assert_eq!(outcomes[0].result, Ok(Answer::Number(514579)));
+        assert_eq!(outcomes[1].result, Ok(Answer::Number(241861950)));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let outcomes = [
+            Outcome {
+                year: 2020,
+                day: 13,
+                part: Part::Two,
+                result: Ok(Answer::Number(535296695251210)),
+                duration: Duration::from_micros(12),
+                timed_out: false,
+                cached: true,
+            },
+            Outcome::failed(
+                registry::find(2020, 19).unwrap(),
+                Part::One,
+                AocError::new("missing rules"),
+            ),
+        ];
+        assert_eq!(
+            to_json(&outcomes).to_string(),
+            concat!(
+                r#"[{"year":2020,"day":13,"part":2,"answer":535296695251210,"duration_ns":12000,"status":"ok","error":null,"cached":true},"#,
+                r#"{"year":2020,"day":19,"part":1,"answer":null,"duration_ns":0,"status":"error","error":"missing rules","cached":false}]"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_run_parse_error() {
+        let day = registry::find(2020, 1).unwrap();
+        let outcomes = run(day, "seven\n", &[Part::One, Part::Two]);
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes.iter().all(|outcome| !outcome.is_ok()));
+    }
+
+    #[test]
+    fn test_run_input_location() {
+        let day = registry::find(2020, 1).unwrap();
+        let input = Input {
+            text: "1721\nseven\n".to_string(),
+            origin: "aoc1.dat".to_string(),
+        };
+        let outcomes = run_input(day, &input, &[Part::One]);
+        let err = outcomes[0].result.as_ref().unwrap_err();
+        assert_eq!(err.to_string(), "aoc1.dat:2:1: invalid number 'seven'");
+    }
+
+    #[test]
+    fn test_run_parallel() {
+        let input = |text: &str| Input {
+            text: text.to_string(),
+            origin: "test".to_string(),
+        };
+        let jobs = vec![
+            (
+                registry::find(2020, 1).unwrap(),
+                input("1721\n979\n366\n299\n675\n1456\n"),
+            ),
+            // Part 2 plays 30 million turns, which takes far too long
+            // for the limit in a debug build.
+            (registry::find(2020, 15).unwrap(), input("0,3,6\n")),
+            (registry::find(2020, 5).unwrap(), input("FBFBBFFRLR\n")),
+        ];
+        let parts = [Part::One, Part::Two];
+        let outcomes = run_parallel(jobs, &parts, 2, Some(Duration::from_millis(200)));
+        let days: Vec<u32> = outcomes.iter().map(|outcome| outcome.day).collect();
+        assert_eq!(days, vec![1, 1, 15, 15, 5, 5]);
+        assert_eq!(outcomes[0].result, Ok(Answer::Number(514579)));
+        assert_eq!(outcomes[2].result, Ok(Answer::Number(436)));
+        assert!(outcomes[3].timed_out);
+        assert_eq!(outcomes[4].result, Ok(Answer::Number(357)));
+        assert_eq!(
+            outcomes.iter().filter(|outcome| outcome.timed_out).count(),
+            1
+        );
+        assert_eq!(
+            to_json(&outcomes[3..4]).as_array().unwrap()[0].get("status"),
+            Some(&Value::from("timeout"))
+        );
+    }
+}
@@ -0,0 +1,499 @@
+//! Scaffolding for a new day.
+//!
+//! A new day N gets a solver module `src/dayNN.rs` with a test stub,
+//! which is declared in `src/lib.rs` and added to the registry, a
+//! binary `src/bin/aocN.rs`, and empty files for the input and the
+//! first example, together with an expected-answer file for the
+//! example where the answers can be filled in.
+//!
+//! Days of later years go into the module of the year instead, like
+//! `src/y2021/day01.rs` declared in `src/y2021/mod.rs`, which is
+//! created with the first day of the year. Their input files are kept
+//! in a directory per year, like `src/bin/2021`, and they have no
+//! binary of their own but are run with `aoc run`.
+//!
+//! Nothing is changed if any of the files already exist or the day is
+//! already registered.
+
+use crate::{
+    error::{AocError, Result},
+    input,
+    registry::{self, FIRST_YEAR},
+};
+use std::{
+    fs::{create_dir_all, read_to_string, write, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Maximum line width used when laying out the registry imports, the
+/// same as rustfmt uses by default.
+const MAX_WIDTH: usize = 100;
+
+fn module_template(day: u32, title: Option<&str>) -> String {
+    let header = match title {
+        Some(title) => format!("//! Day {}: {}", day, title),
+        None => format!("//! Day {}", day),
+    };
+    format!(
+        r#"{header}
+
+use crate::{{
+    error::{{AocError, Result}},
+    Solution,
+}};
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, text: &str) -> Result<Vec<String>> {{
+        Ok(text.lines().map(String::from).collect())
+    }}
+
+    fn part1(&self, _lines: &Vec<String>) -> Result<usize> {{
+        Err(AocError::new("part 1 is not solved yet"))
+    }}
+
+    fn part2(&self, _lines: &Vec<String>) -> Result<usize> {{
+        Err(AocError::new("part 2 is not solved yet"))
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn test_example() {{
+        let input = Day{day:02}.parse(EXAMPLE).unwrap();
+        assert_eq!(Day{day:02}.part1(&input), Ok(0));
+    }}
+}}
+"#,
+        header = header,
+        day = day
+    )
+}
+
+fn binary_template(day: u32) -> String {
+    format!(
+        r#"use adventofcode::{{day{day:02}::Day{day:02}, input, Part, Solver}};
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {{
+    let file = input::from_args({year}, {day})?;
+    let input = Day{day:02}
+        .parse_input(&file.text)
+        .map_err(|err| err.in_file(&file.origin))?;
+    println!("Part 1: {{}}", Day{day:02}.solve_part(input.as_ref(), Part::One)?);
+    println!("Part 2: {{}}", Day{day:02}.solve_part(input.as_ref(), Part::Two)?);
+    Ok(())
+}}
+"#,
+        year = FIRST_YEAR,
+        day = day
+    )
+}
+
+fn year_module_template(year: u32, day: u32) -> String {
+    format!(
+        "//! Solutions to [Advent of Code {year}](https://adventofcode.com/{year}).\n\n\
+         pub mod day{day:02};\n",
+        year = year,
+        day = day
+    )
+}
+
+const EXPECTED_TEMPLATE: &str = "\
+# Expected answers for the example, see the expected module.
+# part1:
+# part2:
+";
+
+/// Number of the day in a line like `pub mod day07;`.
+fn module_day(line: &str) -> Option<u32> {
+    line.strip_prefix("pub mod day")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+/// Declare the module of the day in `lib.rs`, or in the module of a
+/// later year, keeping the day modules in order.
+fn add_module(lib: &str, day: u32) -> Result<String> {
+    let lines: Vec<&str> = lib.lines().collect();
+    let days: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| module_day(line).map(|n| (index, n)))
+        .collect();
+    if days.iter().any(|&(_, n)| n == day) {
+        return Err(AocError::new(format!(
+            "module day{:02} already declared",
+            day
+        )));
+    }
+    let index = match days.iter().find(|&&(_, n)| n > day) {
+        Some(&(index, _)) => index,
+        None => days
+            .last()
+            .map(|&(index, _)| index + 1)
+            .ok_or("no day modules in lib.rs")?,
+    };
+    let declaration = format!("pub mod day{:02};", day);
+    let mut result: Vec<&str> = lines[..index].to_vec();
+    result.push(&declaration);
+    result.extend(&lines[index..]);
+    Ok(result.join("\n") + "\n")
+}
+
+/// Lay out a `use crate::{...};` item the way rustfmt does.
+fn format_use(items: &[String]) -> String {
+    let mut out = String::from("use crate::{\n");
+    let mut line = String::new();
+    for item in items {
+        let next = format!("{},", item);
+        if !line.is_empty() && 4 + line.len() + 1 + next.len() > MAX_WIDTH {
+            out.push_str(&format!("    {}\n", line));
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&next);
+    }
+    if !line.is_empty() {
+        out.push_str(&format!("    {}\n", line));
+    }
+    out.push_str("};");
+    out
+}
+
+/// Year and number of each entry in the table of days of the
+/// registry, with the position where the entry starts.
+fn registry_entries(registry: &str) -> Vec<(usize, u32, u32)> {
+    let field = |line: &str, name: &str| -> Option<u32> {
+        let digits: String = line
+            .trim_start()
+            .strip_prefix(name)?
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        digits.parse().ok()
+    };
+    registry
+        .match_indices("    Day {\n")
+        .filter_map(|(start, pattern)| {
+            let mut lines = registry[start + pattern.len()..].lines();
+            let year = field(lines.next()?, "year: ")?;
+            let number = field(lines.next()?, "number: ")?;
+            Some((start, year, number))
+        })
+        .collect()
+}
+
+/// Add the day to the registry: import the solver (or the module of
+/// the year for later years), add an entry to the table of days in
+/// order, and update the size of the table.
+fn add_to_registry(registry: &str, year: u32, day: u32) -> Result<String> {
+    let malformed = || AocError::new("cannot find the table of days in registry.rs");
+    let table = registry.find("static DAYS").ok_or_else(malformed)?;
+    if registry_entries(&registry[table..])
+        .iter()
+        .any(|&(_, y, n)| (y, n) == (year, day))
+    {
+        return Err(AocError::new(format!(
+            "day {} of {} is already registered",
+            day, year
+        )));
+    }
+    let (import, solver) = match year {
+        FIRST_YEAR => (
+            format!("day{:02}::Day{:02}", day, day),
+            format!("Day{:02}", day),
+        ),
+        _ => (
+            format!("y{}", year),
+            format!("y{}::day{:02}::Day{:02}", year, day, day),
+        ),
+    };
+
+    // Import the solver.
+    let start = registry.find("use crate::{").ok_or_else(malformed)?;
+    let end = start + registry[start..].find("};").ok_or_else(malformed)? + 2;
+    let body = &registry[start + "use crate::{".len()..end - 2];
+    let mut items: Vec<String> = body
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect();
+    if !items.contains(&import) {
+        items.push(import);
+    }
+    // Modules first and then types, like rustfmt.
+    items.sort_by_key(|item| (item.starts_with(char::is_uppercase), item.clone()));
+    let registry = format!(
+        "{}{}{}",
+        &registry[..start],
+        format_use(&items),
+        &registry[end..]
+    );
+
+    // Update the size of the table.
+    let size_start =
+        registry.find("static DAYS: [Day; ").ok_or_else(malformed)? + "static DAYS: [Day; ".len();
+    let size_end = size_start + registry[size_start..].find(']').ok_or_else(malformed)?;
+    let size: usize = registry[size_start..size_end]
+        .parse()
+        .map_err(|_| malformed())?;
+    let registry = format!(
+        "{}{}{}",
+        &registry[..size_start],
+        size + 1,
+        &registry[size_end..]
+    );
+
+    // Add the entry before the first day after it, or last.
+    let entry = format!(
+        "    Day {{\n        year: {},\n        number: {},\n        solver: &{},\n    }},\n",
+        year, day, solver
+    );
+    let table = registry.find("static DAYS").ok_or_else(malformed)?;
+    let table_end = table + registry[table..].find("\n];").ok_or_else(malformed)? + 1;
+    let position = registry_entries(&registry[table..table_end])
+        .into_iter()
+        .find(|&(_, y, n)| (y, n) > (year, day))
+        .map_or(table_end, |(start, _, _)| table + start);
+    Ok(format!(
+        "{}{}{}",
+        &registry[..position],
+        entry,
+        &registry[position..]
+    ))
+}
+
+/// Name of the module in a line like `pub mod docs;`.
+fn module_name(line: &str) -> Option<&str> {
+    line.strip_prefix("pub ")
+        .unwrap_or(line)
+        .strip_prefix("mod ")?
+        .strip_suffix(';')
+}
+
+/// Declare the module of a later year in `lib.rs`, keeping the modules
+/// in order.
+fn add_year_module(lib: &str, year: u32) -> Result<String> {
+    let name = format!("y{}", year);
+    let lines: Vec<&str> = lib.lines().collect();
+    let modules: Vec<(usize, &str)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| module_name(line).map(|module| (index, module)))
+        .collect();
+    if modules.iter().any(|&(_, module)| module == name) {
+        return Err(AocError::new(format!("module {} already declared", name)));
+    }
+    let index = match modules.iter().find(|&&(_, module)| module > name.as_str()) {
+        Some(&(index, _)) => index,
+        None => modules
+            .last()
+            .map(|&(index, _)| index + 1)
+            .ok_or("no modules in lib.rs")?,
+    };
+    let declaration = format!("pub mod {};", name);
+    let mut result: Vec<&str> = lines[..index].to_vec();
+    result.push(&declaration);
+    result.extend(&lines[index..]);
+    Ok(result.join("\n") + "\n")
+}
+
+/// Create the files for a new day of a year in the source tree at
+/// `root`. Returns the files that were created or changed.
+pub fn create(root: &Path, year: u32, day: u32, title: Option<&str>) -> Result<Vec<PathBuf>> {
+    let src = root.join("src");
+    let data = input::year_dir(&src.join("bin"), year);
+    let mut new_files = vec![
+        (
+            root.join(registry::source_path(year, day)),
+            module_template(day, title),
+        ),
+        (data.join(format!("aoc{}.dat", day)), String::new()),
+        (data.join(format!("aoc{}ex.dat", day)), String::new()),
+        (
+            data.join(format!("aoc{}ex.expected", day)),
+            EXPECTED_TEMPLATE.to_string(),
+        ),
+    ];
+    // Only the first year has a binary per day.
+    if year == FIRST_YEAR {
+        new_files.push((
+            src.join("bin").join(format!("aoc{}.rs", day)),
+            binary_template(day),
+        ));
+    }
+    let existing: Vec<String> = new_files
+        .iter()
+        .filter(|(path, _)| path.exists())
+        .map(|(path, _)| path.display().to_string())
+        .collect();
+    if !existing.is_empty() {
+        return Err(AocError::new(format!(
+            "not overwriting existing files: {}",
+            existing.join(", ")
+        )));
+    }
+
+    // Compute all changes before writing anything, so that nothing is
+    // left half done if the day cannot be added.
+    let read = |path: &Path| {
+        read_to_string(path).map_err(|err| AocError::new(format!("{}: {}", path.display(), err)))
+    };
+    let in_file = |path: &Path| {
+        let file = path.display().to_string();
+        move |err: AocError| err.in_file(&file)
+    };
+    let lib_path = src.join("lib.rs");
+    let registry_path = src.join("registry.rs");
+    let mut changes = vec![];
+    if year == FIRST_YEAR {
+        let lib = add_module(&read(&lib_path)?, day).map_err(in_file(&lib_path))?;
+        changes.push((lib_path, lib));
+    } else {
+        // The module of the year declares the modules of its days.
+        let year_path = src.join(format!("y{}", year)).join("mod.rs");
+        if year_path.exists() {
+            let module = add_module(&read(&year_path)?, day).map_err(in_file(&year_path))?;
+            changes.push((year_path, module));
+        } else {
+            let lib = add_year_module(&read(&lib_path)?, year).map_err(in_file(&lib_path))?;
+            changes.push((lib_path, lib));
+            new_files.push((year_path, year_module_template(year, day)));
+        }
+    }
+    let registry =
+        add_to_registry(&read(&registry_path)?, year, day).map_err(in_file(&registry_path))?;
+    changes.push((registry_path, registry));
+
+    let mut changed = vec![];
+    for (path, contents) in new_files {
+        let io_error = |err| AocError::new(format!("{}: {}", path.display(), err));
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).map_err(io_error)?;
+        }
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(contents.as_bytes()))
+            .map_err(io_error)?;
+        changed.push(path);
+    }
+    for (path, contents) in changes {
+        write(&path, contents)
+            .map_err(|err| AocError::new(format!("{}: {}", path.display(), err)))?;
+        changed.push(path);
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs, process};
+
+    #[test]
+    fn test_add_module() {
+        let lib = "pub mod day01;\npub mod day03;\npub mod docs;\n";
+        assert_eq!(
+            add_module(lib, 2).unwrap(),
+            "pub mod day01;\npub mod day02;\npub mod day03;\npub mod docs;\n"
+        );
+        assert_eq!(
+            add_module(lib, 4).unwrap(),
+            "pub mod day01;\npub mod day03;\npub mod day04;\npub mod docs;\n"
+        );
+        assert!(add_module(lib, 3).is_err());
+    }
+
+    #[test]
+    fn test_add_to_registry() {
+        let registry =
+            read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/src/registry.rs")).unwrap();
+        let updated = add_to_registry(&registry, 2020, 21).unwrap();
+        assert!(updated.contains("day19::Day19, day21::Day21, Solver,\n};"));
+        assert!(updated.contains("static DAYS: [Day; 20] = ["));
+        assert!(updated.contains(
+            "        solver: &Day19,\n    },\n    Day {\n        year: 2020,\n        number: 21,\n"
+        ));
+        assert!(add_to_registry(&updated, 2020, 21).is_err());
+
+        // Days of later years come after all days of earlier years.
+        let updated = add_to_registry(&updated, 2030, 2).unwrap();
+        let updated = add_to_registry(&updated, 2030, 1).unwrap();
+        assert!(updated.contains("day21::Day21, y2030, Solver,\n};"));
+        assert!(updated.contains("static DAYS: [Day; 22] = ["));
+        assert!(updated.contains(
+            "        solver: &Day21,\n    },\n    Day {\n        year: 2030,\n        number: 1,\n        \
+             solver: &y2030::day01::Day01,\n    },\n    Day {\n        year: 2030,\n        number: 2,\n"
+        ));
+    }
+
+    #[test]
+    fn test_add_year_module() {
+        let lib = "pub mod day01;\nmod solution;\npub mod watch;\n\npub use error::AocError;\n";
+        assert_eq!(
+            add_year_module(lib, 2030).unwrap(),
+            "pub mod day01;\nmod solution;\npub mod watch;\npub mod y2030;\n\npub use error::AocError;\n"
+        );
+        assert!(add_year_module("pub mod y2030;\n", 2030).is_err());
+    }
+
+    #[test]
+    fn test_create() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        let src = root.join("src");
+        fs::create_dir_all(src.join("bin")).unwrap();
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+        fs::copy(manifest.join("src/lib.rs"), src.join("lib.rs")).unwrap();
+        fs::copy(manifest.join("src/registry.rs"), src.join("registry.rs")).unwrap();
+
+        let changed = create(&root, 2020, 21, Some("Allergen Assessment")).unwrap();
+        assert_eq!(changed.len(), 7);
+        let module = read_to_string(src.join("day21.rs")).unwrap();
+        assert!(module.starts_with("//! Day 21: Allergen Assessment\n"));
+        assert_eq!(read_to_string(src.join("bin/aoc21.dat")).unwrap(), "");
+        assert!(read_to_string(src.join("lib.rs"))
+            .unwrap()
+            .contains("pub mod day21;"));
+
+        // Nothing is overwritten the second time.
+        fs::write(src.join("bin/aoc21.dat"), "input").unwrap();
+        assert!(create(&root, 2020, 21, None).is_err());
+        assert_eq!(read_to_string(src.join("bin/aoc21.dat")).unwrap(), "input");
+
+        // The first day of a later year adds the module of the year,
+        // and later days are added to it.
+        let changed = create(&root, 2030, 1, None).unwrap();
+        assert_eq!(changed.len(), 7);
+        assert!(src.join("y2030/day01.rs").is_file());
+        assert!(src.join("bin/2030/aoc1ex.expected").is_file());
+        assert!(!src.join("bin/aoc1.rs").exists());
+        assert!(read_to_string(src.join("lib.rs"))
+            .unwrap()
+            .contains("pub mod y2030;"));
+        create(&root, 2030, 2, None).unwrap();
+        assert_eq!(
+            read_to_string(src.join("y2030/mod.rs")).unwrap(),
+            "//! Solutions to [Advent of Code 2030](https://adventofcode.com/2030).\n\n\
+             pub mod day01;\npub mod day02;\n"
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,262 @@
+//! Local HTTP server exposing the solvers.
+//!
+//! Other programs can solve a part by posting the puzzle input to
+//! `/{year}/day/{n}/part/{p}`, or to `/day/{n}/part/{p}` for the year
+//! the server was started with:
+//!
+//! ```text
+//! curl --data-binary @src/bin/aoc13.dat http://127.0.0.1:2020/2020/day/13/part/2
+//! ```
+//!
+//! The response is a JSON object as written by `runner::outcome_json`,
+//! with status 200 if the part was solved and 422 if the input was
+//! rejected or the solver failed. Other errors, like an unknown path or
+//! a day that is not solved, are answered with an object holding just
+//! an `error` message.
+//!
+//! Only the standard library is used: each connection is handled on
+//! its own thread and answered with HTTP/1.0, closing the connection
+//! afterwards.
+
+use crate::{
+    error::{AocError, Result},
+    json::Value,
+    registry, runner, Part,
+};
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+/// How long to wait for a client to send its request.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Largest request body accepted, well above any puzzle input.
+const MAX_BODY: usize = 16 << 20;
+
+/// Largest request head accepted.
+const MAX_HEAD: usize = 64 << 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+/// A response with its status and JSON body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Response {
+        Response {
+            status,
+            body: Value::object(vec![("error", Value::from(message))]),
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        _ => "Error",
+    }
+}
+
+/// Read a request from a client. Errors are answered with the
+/// response to send back.
+fn read_request(stream: &mut impl Read) -> std::result::Result<Request, Response> {
+    let bad = |message: &str| Response::error(400, message);
+    let mut raw = vec![];
+    let mut buf = [0; 8192];
+    let (head_end, length) = loop {
+        let n = stream.read(&mut buf).map_err(|err| bad(&err.to_string()))?;
+        if n == 0 {
+            return Err(bad("incomplete request"));
+        }
+        raw.extend_from_slice(&buf[..n]);
+        if let Some(pos) = raw.windows(4).position(|window| window == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(&raw[..pos]);
+            let length = head
+                .lines()
+                .skip(1)
+                .filter_map(|line| line.split_once(':'))
+                .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+                .map_or(Ok(0), |(_, value)| value.trim().parse())
+                .map_err(|_| bad("bad Content-Length"))?;
+            break (pos + 4, length);
+        }
+        if raw.len() > MAX_HEAD {
+            return Err(bad("request head too large"));
+        }
+    };
+    if length > MAX_BODY {
+        return Err(Response::error(413, "input too large"));
+    }
+    while raw.len() < head_end + length {
+        let n = stream.read(&mut buf).map_err(|err| bad(&err.to_string()))?;
+        if n == 0 {
+            return Err(bad("incomplete request"));
+        }
+        raw.extend_from_slice(&buf[..n]);
+    }
+
+    let head = String::from_utf8_lossy(&raw[..head_end]);
+    let mut request_line = head.lines().next().unwrap_or("").split_whitespace();
+    let (method, path) = match (request_line.next(), request_line.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Err(bad("bad request line")),
+    };
+    let body = String::from_utf8(raw[head_end..head_end + length].to_vec())
+        .map_err(|_| bad("input is not UTF-8"))?;
+    Ok(Request { method, path, body })
+}
+
+/// Year, day, and part from a path like `/2020/day/13/part/2`, or
+/// like `/day/13/part/2` for the default year.
+fn parse_path(path: &str, default_year: u32) -> Option<(u32, u32, &str)> {
+    match path.split('/').collect::<Vec<_>>()[..] {
+        ["", "day", day, "part", part] => Some((default_year, day.parse().ok()?, part)),
+        ["", year, "day", day, "part", part] => Some((year.parse().ok()?, day.parse().ok()?, part)),
+        _ => None,
+    }
+}
+
+/// Answer a request, where paths without a year are for `year`.
+pub fn handle(year: u32, request: &Request) -> Response {
+    let (year, number, part) = match parse_path(&request.path, year) {
+        Some(route) => route,
+        None => return Response::error(404, "not found, use POST /{year}/day/{n}/part/{p}"),
+    };
+    if request.method != "POST" {
+        return Response::error(405, "use POST with the puzzle input as body");
+    }
+    let day = match registry::find(year, number) {
+        Some(day) => day,
+        None => {
+            let message = format!("day {} of {} is not solved", number, year);
+            return Response::error(404, &message);
+        }
+    };
+    let part: Part = match part.parse() {
+        Ok(part) => part,
+        Err(err) => return Response::error(400, &err),
+    };
+    let outcome = runner::run(day, &request.body, &[part]).remove(0);
+    Response {
+        status: if outcome.is_ok() { 200 } else { 422 },
+        body: runner::outcome_json(&outcome),
+    }
+}
+
+fn respond(stream: &mut TcpStream, response: &Response) -> std::io::Result<()> {
+    let body = format!("{}\n", response.body);
+    let head = format!(
+        "HTTP/1.0 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        body.len()
+    );
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body.as_bytes())
+}
+
+fn connection(mut stream: TcpStream, year: u32) {
+    let _ = stream.set_read_timeout(Some(TIMEOUT));
+    let response = match read_request(&mut stream) {
+        Ok(request) => handle(year, &request),
+        Err(response) => response,
+    };
+    // The client may be gone already, and there is nobody to tell.
+    let _ = respond(&mut stream, &response);
+}
+
+/// Answer requests on the listener, each on its own thread, where
+/// paths without a year are for `year`. Only returns if accepting a
+/// connection fails.
+pub fn serve(listener: &TcpListener, year: u32) -> Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream.map_err(|err| AocError::new(format!("cannot accept: {}", err)))?;
+        thread::spawn(move || connection(stream, year));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{http, json};
+
+    fn post(path: &str, body: &str) -> Request {
+        Request {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_read_request() {
+        let raw = "POST /day/1/part/1 HTTP/1.1\r\nHost: x\r\ncontent-length: 4\r\n\r\n1\n2\nextra";
+        assert_eq!(
+            read_request(&mut raw.as_bytes()),
+            Ok(post("/day/1/part/1", "1\n2\n"))
+        );
+        let truncated = "POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 10\r\n\r\n1\n";
+        assert_eq!(
+            read_request(&mut truncated.as_bytes()).unwrap_err().status,
+            400
+        );
+    }
+
+    #[test]
+    fn test_handle() {
+        let response = handle(
+            2020,
+            &post("/day/13/part/2", "939\r\n7,13,x,x,59,x,31,19\r\n"),
+        );
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body.get("answer"), Some(&Value::Int(1068781)));
+        assert_eq!(response.body.get("status"), Some(&Value::from("ok")));
+
+        let response = handle(2020, &post("/2020/day/13/part/2", "939\n"));
+        assert_eq!(response.status, 422);
+        assert_eq!(response.body.get("status"), Some(&Value::from("error")));
+
+        assert_eq!(handle(2020, &post("/day/20/part/1", "")).status, 404);
+        assert_eq!(handle(2020, &post("/2019/day/1/part/1", "")).status, 404);
+        assert_eq!(handle(2020, &post("/day/1/part/3", "")).status, 400);
+        assert_eq!(handle(2020, &post("/days", "")).status, 404);
+        let get = Request {
+            method: "GET".to_string(),
+            ..post("/day/1/part/1", "")
+        };
+        assert_eq!(handle(2020, &get).status, 405);
+    }
+
+    #[test]
+    fn test_serve() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || serve(&listener, 2020));
+
+        let response =
+            http::post(&format!("{}/day/18/part/1", url), &[], "2 * 3 + (4 * 5)\n").unwrap();
+        assert_eq!(response.status, 200);
+        let body = json::parse(&response.body).unwrap();
+        assert_eq!(body.get("answer"), Some(&Value::Int(26)));
+        assert_eq!(body.get("day"), Some(&Value::Int(18)));
+    }
+}
@@ -0,0 +1,108 @@
+//! Access to the puzzle site.
+//!
+//! The site is reached at a base URL, below which each year has its
+//! own pages. It can be changed with `AOC_BASE_URL`, for example to
+//! test against a local server. Requests for puzzle input and answers
+//! are per user, so they carry the session cookie of a logged in
+//! user, taken from `AOC_SESSION`.
+
+use crate::{
+    error::{AocError, Result},
+    http::{self, Response},
+};
+use std::env;
+
+/// Environment variable with the base URL of the site.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Environment variable with the session token.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The site asks automated tools to say who they are.
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    " aoc tool (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    pub base_url: String,
+    pub session: Option<String>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: Option<&str>) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.map(String::from),
+        }
+    }
+
+    /// Client using `AOC_BASE_URL` and `AOC_SESSION`, unless a base
+    /// URL is given.
+    pub fn from_env(base_url: Option<&str>) -> Client {
+        let env_url = env::var(BASE_URL_VAR).ok();
+        let base_url = base_url.or(env_url.as_deref()).unwrap_or(DEFAULT_BASE_URL);
+        let session = env::var(SESSION_VAR).ok().filter(|s| !s.trim().is_empty());
+        Client::new(base_url, session.as_deref().map(str::trim))
+    }
+
+    /// URL of a page of a day of a year, such as `/input`.
+    pub fn day_url(&self, year: u32, day: u32, page: &str) -> String {
+        format!("{}/{}/day/{}{}", self.base_url, year, day, page)
+    }
+
+    fn cookie(&self) -> Result<String> {
+        match &self.session {
+            Some(session) => Ok(format!("session={}", session)),
+            None => Err(AocError::new(format!(
+                "no session token, set {} to the session cookie of the site",
+                SESSION_VAR
+            ))),
+        }
+    }
+
+    /// Check that the response is successful.
+    fn check(url: &str, response: Response) -> Result<Response> {
+        if response.is_success() {
+            Ok(response)
+        } else {
+            let reason = response.body.lines().next().unwrap_or("").trim();
+            Err(AocError::new(format!(
+                "{}: HTTP status {}: {}",
+                url, response.status, reason
+            )))
+        }
+    }
+
+    pub fn get(&self, url: &str) -> Result<Response> {
+        let cookie = self.cookie()?;
+        let headers = [("Cookie", cookie.as_str()), ("User-Agent", USER_AGENT)];
+        Client::check(url, http::get(url, &headers)?)
+    }
+
+    pub fn post(&self, url: &str, form: &str) -> Result<Response> {
+        let cookie = self.cookie()?;
+        let headers = [("Cookie", cookie.as_str()), ("User-Agent", USER_AGENT)];
+        Client::check(url, http::post(url, &headers, form)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_url() {
+        let client = Client::new("http://127.0.0.1:8000/", None);
+        assert_eq!(
+            client.day_url(2020, 7, "/input"),
+            "http://127.0.0.1:8000/2020/day/7/input"
+        );
+        assert!(client.get(&client.day_url(2020, 7, "/input")).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{error::Result, input::normalize};
+use std::{any::Any, fmt, str::FromStr};
+
+/// Answer to one part of a puzzle.
+///
+/// Almost all answers are numbers, but some puzzles ask for a string,
+/// so both are supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => n.fmt(f),
+            Answer::Text(s) => f.pad(s),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(n as i64)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// Part of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.number().fmt(f)
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(text: &str) -> std::result::Result<Part, String> {
+        match text {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("part has to be 1 or 2, not '{}'", text)),
+        }
+    }
+}
+
+/// Solution to the puzzles of one day.
+///
+/// The puzzle input is parsed once and then shared by both parts, so
+/// all the work that is common to the parts should go into `parse`.
+/// Parse errors should carry the line and column of the problem, see
+/// [`AocError`](crate::AocError).
+pub trait Solution {
+    /// Parsed puzzle input.
+    type Input;
+
+    /// Answer to the first part.
+    type Part1: Into<Answer>;
+
+    /// Answer to the second part.
+    type Part2: Into<Answer>;
+
+    fn parse(&self, text: &str) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Result<Self::Part1>;
+    fn part2(&self, input: &Self::Input) -> Result<Self::Part2>;
+}
+
+/// Type-erased version of [`Solution`].
+///
+/// This is what the registry hands out, so that days with different
+/// input and answer types can be stored and called uniformly. It is
+/// implemented for every [`Solution`] and there should be no reason
+/// to implement it directly. The text is
+/// [normalized](crate::input::normalize) before it is parsed.
+pub trait Solver {
+    fn parse_input(&self, text: &str) -> Result<Box<dyn Any>>;
+    fn solve_part(&self, input: &dyn Any, part: Part) -> Result<Answer>;
+
+    /// Parse the text and solve one part of the puzzle.
+    fn solve(&self, text: &str, part: Part) -> Result<Answer> {
+        let input = self.parse_input(text)?;
+        self.solve_part(input.as_ref(), part)
+    }
+}
+
+impl<S> Solver for S
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn parse_input(&self, text: &str) -> Result<Box<dyn Any>> {
+        let input = self.parse(&normalize(text))?;
+        Ok(Box::new(input))
+    }
+
+    fn solve_part(&self, input: &dyn Any, part: Part) -> Result<Answer> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input was not produced by this solver");
+        match part {
+            Part::One => self.part1(input).map(Into::into),
+            Part::Two => self.part2(input).map(Into::into),
+        }
+    }
+}
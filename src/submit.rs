@@ -0,0 +1,369 @@
+//! Submitting answers.
+//!
+//! Answers are posted to the site, and the page that comes back is
+//! read to find out if the answer was right. Judged answers are
+//! recorded in `aocN.submitted` next to the input, one per line:
+//!
+//! ```text
+//! part1 too-high 1398413738
+//! part1 correct 692916
+//! ```
+//!
+//! The record is checked before submitting, so an answer that is
+//! known to be wrong is never sent again. Numeric answers are also
+//! checked against the bounds given by earlier "too high" and "too
+//! low" answers.
+
+use crate::{
+    error::{AocError, Result},
+    site::Client,
+    Answer, Part,
+};
+use std::{
+    fmt,
+    fs::{read_to_string, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+/// What the site said about an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// An answer was given too recently. The time left to wait is
+    /// included when the site gives it.
+    Wait(Option<Duration>),
+    /// The part is already solved, or not yet unlocked.
+    AlreadyCompleted,
+    /// Could not make sense of the response.
+    Unknown,
+}
+
+impl Verdict {
+    /// Whether the verdict says something about the answer, and so
+    /// should be recorded.
+    pub fn is_judgement(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Correct | Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::Wait(_) => "wait",
+            Verdict::AlreadyCompleted => "already-completed",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(Some(left)) => write!(f, "wait {} seconds", left.as_secs()),
+            Verdict::Wait(None) => write!(f, "wait before submitting again"),
+            Verdict::AlreadyCompleted => write!(f, "already completed"),
+            Verdict::Unknown => write!(f, "unknown response"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<Verdict> {
+        match text {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            _ => Err(AocError::bad("unknown verdict", text)),
+        }
+    }
+}
+
+/// Parse a wait time like `1m 5s` into a duration.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let mut seconds = 0;
+    for part in text.split_whitespace() {
+        let (number, unit) = part.split_at(part.find(|ch: char| !ch.is_ascii_digit())?);
+        let number: u64 = number.parse().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Read the verdict from the page returned for an answer.
+pub fn parse_verdict(page: &str) -> Verdict {
+    if page.contains("That's the right answer") {
+        Verdict::Correct
+    } else if page.contains("You gave an answer too recently") {
+        let left = page
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .and_then(|(time, _)| parse_wait(time));
+        Verdict::Wait(left)
+    } else if page.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadyCompleted
+    } else if page.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if page.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if page.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else {
+        Verdict::Unknown
+    }
+}
+
+/// Answers submitted for a day, with what the site said about them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    pub entries: Vec<(Part, Verdict, String)>,
+}
+
+impl History {
+    pub fn parse(text: &str) -> Result<History> {
+        let mut entries = vec![];
+        for (lineno, line) in text.lines().enumerate() {
+            let mut words = line.splitn(3, ' ');
+            let (part, verdict, answer) = match (words.next(), words.next(), words.next()) {
+                (Some(part), Some(verdict), Some(answer)) => (part, verdict, answer),
+                _ if line.trim().is_empty() => continue,
+                _ => {
+                    return Err(AocError::new("expected 'partN verdict answer'").at_line(lineno + 1))
+                }
+            };
+            let part = part
+                .strip_prefix("part")
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| {
+                    AocError::bad("bad part", part)
+                        .at_column(1)
+                        .at_line(lineno + 1)
+                })?;
+            let verdict = verdict
+                .parse()
+                .map_err(|err: AocError| err.at_token(line, verdict).at_line(lineno + 1))?;
+            entries.push((part, verdict, answer.to_string()));
+        }
+        Ok(History { entries })
+    }
+
+    /// Read the history from a file, which may not exist yet.
+    pub fn read(path: &Path) -> Result<History> {
+        let file = path.display().to_string();
+        match read_to_string(path) {
+            Ok(text) => History::parse(&text).map_err(|err| err.in_file(&file)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(AocError::new(format!("{}: {}", file, err))),
+        }
+    }
+
+    /// Reason not to submit an answer, if the history already tells
+    /// whether it is right.
+    pub fn check(&self, part: Part, answer: &Answer) -> Option<String> {
+        let text = answer.to_string();
+        let entries = self.entries.iter().filter(|(p, _, _)| *p == part);
+        for (_, verdict, previous) in entries.clone() {
+            if *previous == text {
+                return Some(format!(
+                    "{} was already submitted and was {}",
+                    text, verdict
+                ));
+            }
+            if *verdict == Verdict::Correct {
+                return Some(format!(
+                    "part {} was already solved with {}",
+                    part, previous
+                ));
+            }
+        }
+        if let Answer::Number(n) = *answer {
+            for (_, verdict, previous) in entries {
+                let bound = match previous.parse::<i64>() {
+                    Ok(bound) => bound,
+                    Err(_) => continue,
+                };
+                match verdict {
+                    Verdict::TooHigh if n > bound => {
+                        return Some(format!("{} is too high, since {} was too high", n, bound))
+                    }
+                    Verdict::TooLow if n < bound => {
+                        return Some(format!("{} is too low, since {} was too low", n, bound))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+}
+
+/// File with the submitted answers of a day.
+pub fn history_path(dir: &Path, day: u32) -> PathBuf {
+    dir.join(format!("aoc{}.submitted", day))
+}
+
+fn record(path: &Path, part: Part, verdict: &Verdict, answer: &Answer) -> Result<()> {
+    let io_error = |err| AocError::new(format!("{}: {}", path.display(), err));
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(io_error)?;
+    writeln!(file, "part{} {} {}", part, verdict.name(), answer).map_err(io_error)
+}
+
+/// Percent-encode a value for a form.
+fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Submit an answer for a part of a day of a year, unless the history
+/// of the day in `dir` already tells whether it is right. Judged
+/// answers are added to the history.
+pub fn submit(
+    client: &Client,
+    dir: &Path,
+    year: u32,
+    day: u32,
+    part: Part,
+    answer: &Answer,
+) -> Result<Verdict> {
+    let path = history_path(dir, day);
+    if let Some(reason) = History::read(&path)?.check(part, answer) {
+        return Err(AocError::new(format!("not submitting: {}", reason)));
+    }
+
+    let form = format!("level={}&answer={}", part, form_encode(&answer.to_string()));
+    let response = client.post(&client.day_url(year, day, "/answer"), &form)?;
+    let verdict = parse_verdict(&response.body);
+    if verdict.is_judgement() {
+        record(&path, part, &verdict, answer)?;
+    }
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::tests::serve;
+    use std::{env, fs, process};
+
+    #[test]
+    fn test_parse_verdict() {
+        let page = |text| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+        assert_eq!(
+            parse_verdict(&page(
+                "That's the right answer! You are one gold star closer."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer; your answer is too high. Please wait one minute."
+            )),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_verdict(&page("That's not the right answer. If you're stuck, ...")),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 5s left to wait."
+            )),
+            Verdict::Wait(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Verdict::AlreadyCompleted
+        );
+        assert_eq!(parse_verdict("<html></html>"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_history_check() {
+        let history =
+            History::parse("part1 too-high 100\npart1 too-low 10\npart1 wrong 42\n").unwrap();
+        assert_eq!(history.check(Part::One, &Answer::Number(50)), None);
+        assert!(history.check(Part::One, &Answer::Number(42)).is_some());
+        assert!(history.check(Part::One, &Answer::Number(100)).is_some());
+        assert!(history.check(Part::One, &Answer::Number(101)).is_some());
+        assert!(history.check(Part::One, &Answer::Number(9)).is_some());
+        assert_eq!(history.check(Part::Two, &Answer::Number(101)), None);
+
+        let history = History::parse("part2 correct 7\n").unwrap();
+        assert!(history.check(Part::Two, &Answer::Number(8)).is_some());
+        assert!(History::parse("part3 correct 7\n").is_err());
+    }
+
+    #[test]
+    fn test_submit() {
+        let dir = env::temp_dir().join(format!("aoc-submit-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (url, server) = serve(vec![
+            (
+                200,
+                "<p>That's not the right answer; your answer is too low.</p>",
+            ),
+            (
+                200,
+                "<p>You gave an answer too recently. You have 30s left to wait.</p>",
+            ),
+        ]);
+        let client = Client::new(&url, Some("abc"));
+
+        let verdict = submit(&client, &dir, 2020, 9, Part::Two, &Answer::Number(1000));
+        assert_eq!(verdict, Ok(Verdict::TooLow));
+        // Known to be too low, so the server is not asked again.
+        assert!(submit(&client, &dir, 2020, 9, Part::Two, &Answer::Number(999)).is_err());
+        let verdict = submit(&client, &dir, 2020, 9, Part::Two, &Answer::Number(2000));
+        assert_eq!(verdict, Ok(Verdict::Wait(Some(Duration::from_secs(30)))));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2020/day/9/answer "));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=1000"));
+        let history = fs::read_to_string(history_path(&dir, 9)).unwrap();
+        assert_eq!(history, "part2 too-low 1000\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,118 @@
+//! Verbose output for debugging.
+//!
+//! Days can show intermediate state with the [`debug!`] and
+//! [`trace!`] macros, which write to standard error prefixed with the
+//! module they are used in. Nothing is written by default, so that
+//! standard output only has the answers. The verbosity is set with
+//! `-v` for debug output and `-vv` for trace output as well, which is
+//! handled by [`take_verbosity`] for the runner and the binaries of
+//! the days.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much to write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Nothing, the default.
+    Quiet,
+    /// Intermediate state, like progress of a long loop.
+    Debug,
+    /// Detailed state, like each step of a search.
+    Trace,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Level::Quiet as u8);
+
+/// Set the verbosity for all threads.
+pub fn set_verbosity(level: Level) {
+    VERBOSITY.store(level as u8, Ordering::Relaxed);
+}
+
+/// The current verbosity.
+pub fn verbosity() -> Level {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Level::Quiet,
+        1 => Level::Debug,
+        _ => Level::Trace,
+    }
+}
+
+/// Whether output at a level is written.
+pub fn enabled(level: Level) -> bool {
+    level <= verbosity()
+}
+
+/// Write a message at a level, prefixed with the last part of the
+/// module path. Use [`debug!`] and [`trace!`] instead.
+#[doc(hidden)]
+pub fn write(module: &str, message: std::fmt::Arguments) {
+    let name = module.rsplit("::").next().unwrap_or(module);
+    eprintln!("{}: {}", name, message);
+}
+
+/// Remove the verbosity options (`-v`, `-vv`, and `--verbose`, which
+/// can be repeated) from the arguments and set the verbosity
+/// accordingly. Returns the other arguments.
+pub fn take_verbosity(args: Vec<String>) -> Vec<String> {
+    let mut count = 0;
+    let mut rest = vec![];
+    for arg in args {
+        match arg.as_str() {
+            "-v" | "--verbose" => count += 1,
+            "-vv" => count += 2,
+            _ => rest.push(arg),
+        }
+    }
+    match count {
+        0 => {}
+        1 => set_verbosity(Level::Debug),
+        _ => set_verbosity(Level::Trace),
+    }
+    rest
+}
+
+/// Write intermediate state when running with `-v` or more. The
+/// arguments are only evaluated when the output is enabled.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled($crate::trace::Level::Debug) {
+            $crate::trace::write(module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+/// Write detailed state when running with `-vv`. The arguments are
+/// only evaluated when the output is enabled.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled($crate::trace::Level::Trace) {
+            $crate::trace::write(module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_verbosity() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(verbosity(), Level::Quiet);
+        assert!(!enabled(Level::Debug));
+        assert_eq!(
+            take_verbosity(args(&["run", "--all", "--jobs", "2"])),
+            args(&["run", "--all", "--jobs", "2"])
+        );
+        assert_eq!(verbosity(), Level::Quiet);
+        assert_eq!(
+            take_verbosity(args(&["-v", "run", "7", "-v"])),
+            args(&["run", "7"])
+        );
+        assert_eq!(verbosity(), Level::Trace);
+        assert!(enabled(Level::Debug) && enabled(Level::Trace));
+        set_verbosity(Level::Quiet);
+    }
+}
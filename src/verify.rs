@@ -0,0 +1,304 @@
+//! Verifying a day against many inputs.
+//!
+//! Everyone gets a different puzzle input, and a solver that works for
+//! one of them can still fail for another. A directory can hold the
+//! inputs of several people, each with its expected answers in an
+//! `.expected` sidecar (see the `expected` module):
+//!
+//! ```text
+//! inputs/alice.dat
+//! inputs/alice.expected
+//! inputs/bob.txt
+//! inputs/bob.expected
+//! ```
+//!
+//! Every file in the directory that is not a sidecar is an input, and
+//! the day is solved for each of them. The result is a matrix with a
+//! row per input and a column per part, followed by the details of the
+//! parts that did not pass. A part whose input has no expected answer
+//! is shown as `?`; it has not been checked, so it does not pass either
+//! unless unchecked parts are allowed:
+//!
+//! ```text
+//! Input      Part 1  Part 2
+//! alice.dat  pass    pass
+//! bob.txt    FAIL    ?
+//!
+//! bob.txt part 1: got 1016131, expected 1016964
+//! bob.txt part 2: got 182588480, no expected answer
+//! ```
+
+use crate::{
+    expected::{self, Expected},
+    input::{self, Source},
+    registry::Day,
+    runner::{self, Outcome},
+    Part,
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Extensions of the files kept next to an input.
+const SIDECARS: [&str; 2] = ["expected", "submitted"];
+
+/// How the answer to a part compares with the expected one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail {
+        answer: String,
+        expected: String,
+    },
+    /// Solved, but there is no expected answer to compare with.
+    Unchecked(String),
+    Error(String),
+    TimedOut,
+}
+
+impl Check {
+    fn new(outcome: &Outcome, expected: Option<&str>) -> Check {
+        match (&outcome.result, expected) {
+            (Err(_), _) if outcome.timed_out => Check::TimedOut,
+            (Err(err), _) => Check::Error(err.to_string()),
+            (Ok(answer), None) => Check::Unchecked(answer.to_string()),
+            (Ok(answer), Some(want)) if answer.to_string() == want => Check::Pass,
+            (Ok(answer), Some(want)) => Check::Fail {
+                answer: answer.to_string(),
+                expected: want.to_string(),
+            },
+        }
+    }
+
+    /// Whether the part passed, where a part without an expected
+    /// answer only does if `allow_unchecked` is set.
+    pub fn is_ok(&self, allow_unchecked: bool) -> bool {
+        match self {
+            Check::Pass => true,
+            Check::Unchecked(_) => allow_unchecked,
+            _ => false,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Check::Pass => "pass",
+            Check::Fail { .. } => "FAIL",
+            Check::Unchecked(_) => "?",
+            Check::Error(_) => "error",
+            Check::TimedOut => "timeout",
+        }
+    }
+
+    fn details(&self) -> Option<String> {
+        match self {
+            Check::Pass => None,
+            Check::Fail { answer, expected } => {
+                Some(format!("got {}, expected {}", answer, expected))
+            }
+            Check::Unchecked(answer) => Some(format!("got {}, no expected answer", answer)),
+            Check::Error(err) => Some(format!("error: {}", err)),
+            Check::TimedOut => Some("timed out".to_string()),
+        }
+    }
+}
+
+/// The checks of the parts for one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// Name of the input file.
+    pub name: String,
+    pub checks: Vec<(Part, Check)>,
+}
+
+impl Row {
+    /// Whether all parts passed, see `Check::is_ok`.
+    pub fn is_ok(&self, allow_unchecked: bool) -> bool {
+        self.checks
+            .iter()
+            .all(|(_, check)| check.is_ok(allow_unchecked))
+    }
+}
+
+/// The inputs in a directory: all files except hidden ones and the
+/// sidecars of inputs, in order of their names.
+pub fn inputs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut inputs = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(true, |name| name.starts_with('.'));
+        let sidecar = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| SIDECARS.contains(&ext));
+        if path.is_file() && !hidden && !sidecar {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// Solve the given parts of a day for each of the inputs, `workers` at
+/// a time and each within `limit` if given, and check the answers
+/// against the sidecars of the inputs.
+pub fn verify(
+    day: &'static Day,
+    paths: &[PathBuf],
+    parts: &[Part],
+    workers: usize,
+    limit: Option<Duration>,
+) -> Vec<Row> {
+    let mut jobs = vec![];
+    let mut loaded: Vec<(usize, Expected)> = vec![];
+    let mut failed: Vec<(usize, String)> = vec![];
+    for (index, path) in paths.iter().enumerate() {
+        let sidecar = expected::sidecar(path);
+        let expected = match Expected::read(&sidecar) {
+            Ok(expected) => Ok(expected),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Expected::default()),
+            Err(err) => Err(err.to_string()),
+        };
+        let input = input::load(day.year, day.number, &Source::Path(path.clone()))
+            .map_err(|err| err.to_string());
+        match (input, expected) {
+            (Ok(input), Ok(expected)) => {
+                jobs.push((day, input));
+                loaded.push((index, expected));
+            }
+            (Err(err), _) | (_, Err(err)) => failed.push((index, err)),
+        }
+    }
+
+    let outcomes = runner::run_parallel(jobs, parts, workers, limit);
+
+    let mut rows: Vec<Row> = paths
+        .iter()
+        .map(|path| Row {
+            name: path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            ),
+            checks: vec![],
+        })
+        .collect();
+    // The outcomes come in the order of the inputs, all parts each.
+    for ((index, expected), outcomes) in loaded.iter().zip(outcomes.chunks(parts.len())) {
+        rows[*index].checks = outcomes
+            .iter()
+            .map(|outcome| {
+                (
+                    outcome.part,
+                    Check::new(outcome, expected.get(outcome.part)),
+                )
+            })
+            .collect();
+    }
+    for (index, err) in failed {
+        rows[index].checks = parts
+            .iter()
+            .map(|&part| (part, Check::Error(err.clone())))
+            .collect();
+    }
+    rows
+}
+
+/// Format the rows as a matrix of inputs and parts, followed by a line
+/// for each part that did not pass.
+pub fn format(rows: &[Row], parts: &[Part]) -> String {
+    let width = rows
+        .iter()
+        .map(|row| row.name.chars().count())
+        .chain(Some("Input".len()))
+        .max()
+        .unwrap_or_default();
+    let mut header = format!("{:<width$}", "Input", width = width);
+    for part in parts {
+        header.push_str(&format!("  Part {}", part));
+    }
+    let mut lines = vec![header.trim_end().to_string()];
+    for row in rows {
+        let mut line = format!("{:<width$}", row.name, width = width);
+        for (_, check) in &row.checks {
+            line.push_str(&format!("  {:<6}", check.label()));
+        }
+        lines.push(line.trim_end().to_string());
+    }
+
+    let details: Vec<String> = rows
+        .iter()
+        .flat_map(|row| {
+            row.checks.iter().filter_map(move |(part, check)| {
+                let details = check.details()?;
+                Some(format!("{} part {}: {}", row.name, part, details))
+            })
+        })
+        .collect();
+    if !details.is_empty() {
+        lines.push(String::new());
+        lines.extend(details);
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use std::{env, process};
+
+    #[test]
+    fn test_verify() {
+        let dir = env::temp_dir().join(format!("aoc-verify-test-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let example = "1721\n979\n366\n299\n675\n1456\n";
+        fs::write(dir.join("alice.dat"), example).unwrap();
+        fs::write(
+            dir.join("alice.expected"),
+            "part1: 514579\npart2: 241861950\n",
+        )
+        .unwrap();
+        fs::write(dir.join("bob.txt"), example).unwrap();
+        fs::write(dir.join("bob.expected"), "part1: 514578\n").unwrap();
+        fs::write(dir.join("carol.dat"), "1721\nseven\n").unwrap();
+        fs::write(dir.join("carol.submitted"), "part1 wrong 1\n").unwrap();
+        fs::write(dir.join(".hidden"), "").unwrap();
+
+        let paths = inputs(&dir).unwrap();
+        let names: Vec<_> = paths.iter().map(|path| path.file_name().unwrap()).collect();
+        assert_eq!(names, ["alice.dat", "bob.txt", "carol.dat"]);
+
+        let parts = [Part::One, Part::Two];
+        let day = registry::find(2020, 1).unwrap();
+        let rows = verify(day, &paths, &parts, 2, None);
+        let unchecked = verify(day, &paths[1..2], &[Part::Two], 1, None);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(!unchecked[0].is_ok(false));
+        assert!(unchecked[0].is_ok(true));
+        assert!(rows[0].is_ok(false));
+        assert!(!rows[1].is_ok(true));
+        assert!(!rows[2].is_ok(true));
+
+        let carol = dir.join("carol.dat").display().to_string();
+        assert_eq!(
+            format(&rows, &parts),
+            format!(
+                "Input      Part 1  Part 2\n\
+                 alice.dat  pass    pass\n\
+                 bob.txt    FAIL    ?\n\
+                 carol.dat  error   error\n\
+                 \n\
+                 bob.txt part 1: got 514579, expected 514578\n\
+                 bob.txt part 2: got 241861950, no expected answer\n\
+                 carol.dat part 1: error: {0}:2:1: invalid number 'seven'\n\
+                 carol.dat part 2: error: {0}:2:1: invalid number 'seven'\n",
+                carol
+            )
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Watching the input of a day.
+//!
+//! The input and example files of a day, together with the expected
+//! answers of the examples, are polled for changes. Whenever one of
+//! them changes, appears, or disappears, the day is solved again for
+//! the input and each example, and the answers that differ from the
+//! previous run are reported. Examples with expected answers are also
+//! checked against them.
+//!
+//! Only the files are watched, not the source of the solver, which
+//! has to be rebuilt for changes to take effect.
+
+use crate::{
+    expected::{self, Expected},
+    input::{self, Resolver, Source},
+    registry::Day,
+    runner, Part,
+};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// Answer to one part for one of the watched inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Name of the input file.
+    pub name: String,
+    pub part: Part,
+    /// The answer, or the error prefixed with `error: `.
+    pub answer: String,
+    /// The expected answer, for examples that have one.
+    pub expected: Option<String>,
+}
+
+impl Entry {
+    fn describe(&self) -> String {
+        match &self.expected {
+            Some(want) if *want == self.answer => format!("{} (ok)", self.answer),
+            Some(want) => format!("{} (expected {})", self.answer, want),
+            None => self.answer.clone(),
+        }
+    }
+}
+
+/// Modification times of the watched files, with `None` for files
+/// that do not exist.
+pub type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// The files to watch for a day: the locations searched for its
+/// input, and the examples found in them with their expected
+/// answers.
+pub fn files(resolver: &Resolver, day: &Day) -> Vec<PathBuf> {
+    let mut files = resolver.candidates(day.year, &input::file_name(day.number));
+    for dir in resolver.dirs(day.year) {
+        let examples = input::find_examples(&dir).unwrap_or_default();
+        for example in examples
+            .into_iter()
+            .filter(|example| example.day == day.number)
+        {
+            files.push(expected::sidecar(&example.path));
+            files.push(example.path);
+        }
+    }
+    files
+}
+
+/// Take a snapshot of the modification times of the files.
+pub fn snapshot(files: &[PathBuf]) -> Snapshot {
+    files
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+/// Numbers of the examples of a day, in order.
+fn example_numbers(files: &[PathBuf], day: u32) -> Vec<u32> {
+    let mut numbers: Vec<u32> = files
+        .iter()
+        .filter_map(|path| path.file_name()?.to_str())
+        .filter_map(input::parse_example_file_name)
+        .filter(|&(example_day, _)| example_day == day)
+        .map(|(_, number)| number)
+        .collect();
+    numbers.sort_unstable();
+    numbers.dedup();
+    numbers
+}
+
+/// Solve the day for its input, if there is one, and for each of its
+/// examples.
+pub fn solve(day: &Day, resolver: &Resolver, files: &[PathBuf]) -> Vec<Entry> {
+    let sources = Some(Source::Search).into_iter().chain(
+        example_numbers(files, day.number)
+            .into_iter()
+            .map(Source::Example),
+    );
+    let mut entries = vec![];
+    for source in sources {
+        let input = match resolver.resolve(day.year, day.number, &source) {
+            Ok(input) => input,
+            Err(_) => continue,
+        };
+        let path = Path::new(&input.origin);
+        let name = path.file_name().map_or(input.origin.clone(), |name| {
+            name.to_string_lossy().to_string()
+        });
+        let expected = match source {
+            Source::Example(_) => Expected::read(&expected::sidecar(path)).unwrap_or_default(),
+            _ => Expected::default(),
+        };
+        for outcome in runner::run_input(day, &input, &[Part::One, Part::Two]) {
+            let answer = match outcome.result {
+                Ok(answer) => answer.to_string(),
+                Err(err) => format!("error: {}", err),
+            };
+            entries.push(Entry {
+                name: name.clone(),
+                part: outcome.part,
+                answer,
+                expected: expected.get(outcome.part).map(String::from),
+            });
+        }
+    }
+    entries
+}
+
+/// Describe how the answers changed since the previous run, one line
+/// per answer that is new, changed, or gone.
+pub fn diff(previous: &[Entry], current: &[Entry]) -> Vec<String> {
+    let find = |entries: &[Entry], entry: &Entry| {
+        entries
+            .iter()
+            .find(|other| other.name == entry.name && other.part == entry.part)
+            .cloned()
+    };
+    let mut lines = vec![];
+    for entry in current {
+        let label = format!("{} part {}", entry.name, entry.part);
+        match find(previous, entry) {
+            Some(old) if old == *entry => {}
+            Some(old) => lines.push(format!(
+                "{}: {} -> {}",
+                label,
+                old.describe(),
+                entry.describe()
+            )),
+            None => lines.push(format!("{}: {}", label, entry.describe())),
+        }
+    }
+    for entry in previous {
+        if find(current, entry).is_none() {
+            lines.push(format!("{} part {}: gone", entry.name, entry.part));
+        }
+    }
+    lines
+}
+
+/// Watch the files of a day, checking every `interval`, and solve the
+/// day whenever they change. The lines describing the changes in the
+/// answers are passed to `report`, starting with all answers of the
+/// first run.
+pub fn watch<F>(day: &Day, resolver: &Resolver, interval: Duration, mut report: F) -> !
+where
+    F: FnMut(&[String]),
+{
+    let mut last = None;
+    let mut entries = vec![];
+    loop {
+        let files = files(resolver, day);
+        let current = snapshot(&files);
+        if last.as_ref() != Some(&current) {
+            let solved = solve(day, resolver, &files);
+            report(&diff(&entries, &solved));
+            entries = solved;
+            last = Some(current);
+        }
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use std::{env, process};
+
+    fn entry(name: &str, part: Part, answer: &str, expected: Option<&str>) -> Entry {
+        Entry {
+            name: name.to_string(),
+            part,
+            answer: answer.to_string(),
+            expected: expected.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let previous = vec![
+            entry("aoc1.dat", Part::One, "514579", None),
+            entry("aoc1.dat", Part::Two, "241861950", None),
+            entry("aoc1ex2.dat", Part::One, "5", Some("5")),
+        ];
+        let current = vec![
+            entry("aoc1.dat", Part::One, "514579", None),
+            entry("aoc1.dat", Part::Two, "error: no answer", None),
+            entry("aoc1ex.dat", Part::One, "7", Some("8")),
+        ];
+        assert_eq!(
+            diff(&previous, &current),
+            vec![
+                "aoc1.dat part 2: 241861950 -> error: no answer",
+                "aoc1ex.dat part 1: 7 (expected 8)",
+                "aoc1ex2.dat part 1: gone",
+            ]
+        );
+        assert!(diff(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_solve() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let resolver = Resolver {
+            input_dir: None,
+            default_dir: dir.clone(),
+        };
+        fs::write(dir.join("aoc1ex.dat"), "1721\n979\n366\n299\n675\n1456\n").unwrap();
+        fs::write(dir.join("aoc1ex.expected"), "part1: 514579\n").unwrap();
+        let day = registry::find(2020, 1).unwrap();
+        let files = files(&resolver, day);
+        let before = snapshot(&files);
+        assert_eq!(before.len(), 3);
+        assert_eq!(before[&dir.join("aoc1.dat")], None);
+
+        let entries = solve(day, &resolver, &files);
+        assert_eq!(
+            entries,
+            vec![
+                entry("aoc1ex.dat", Part::One, "514579", Some("514579")),
+                entry("aoc1ex.dat", Part::Two, "241861950", None),
+            ]
+        );
+
+        fs::write(dir.join("aoc1.dat"), "1010\n1010\n").unwrap();
+        assert_ne!(snapshot(&files), before);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+//! Regression test for the accepted answers.
+//!
+//! The answers accepted for each day are recorded in the module
+//! documentation of the day as "Your puzzle answer was ...". Each
+//! registered day is solved on the puzzle input and the answers
+//! compared with the recorded ones, so that a refactoring cannot
+//! silently change an accepted answer. Days without recorded answers
+//! are skipped: an answer is only recorded once the site accepted it,
+//! never copied from what a solver happens to print.
+//!
+//! Every day, recorded answers or not, is also solved for its puzzle
+//! input as if saved on Windows, which has to give the same answers
+//! as the input itself.
+
+use adventofcode::{
+    docs,
+    input::{self, Input, Source},
+    registry, runner, Part,
+};
+use common::windows_variant;
+use std::{fs::read_to_string, path::PathBuf};
+
+mod common;
+
+#[test]
+fn recorded_answers() {
+    let mut failures = vec![];
+    for day in registry::days() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push(registry::source_path(day.year, day.number));
+        let source = read_to_string(&path).unwrap();
+        let expected = docs::recorded_answers(&docs::module_docs(&source));
+        if expected.parts().is_empty() {
+            continue;
+        }
+
+        let input = input::load(day.year, day.number, &Source::Search).unwrap();
+        for outcome in runner::run_input(day, &input, &expected.parts()) {
+            let want = expected.get(outcome.part).unwrap();
+            match outcome.result {
+                Ok(answer) if answer.to_string() == want => (),
+                Ok(answer) => failures.push(format!(
+                    "{} day {} part {}: recorded {}, got {}",
+                    day.year, day.number, outcome.part, want, answer
+                )),
+                Err(err) => failures.push(format!(
+                    "{} day {} part {}: recorded {}, got error: {}",
+                    day.year, day.number, outcome.part, want, err
+                )),
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "answers differ from the recorded ones:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn windows_line_endings() {
+    let mut jobs = vec![];
+    for day in registry::days() {
+        let input = input::load(day.year, day.number, &Source::Search).unwrap();
+        let windows = Input {
+            text: windows_variant(&input.text),
+            origin: format!("{} (CRLF)", input.origin),
+        };
+        jobs.push((day, input));
+        jobs.push((day, windows));
+    }
+    let parts = [Part::One, Part::Two];
+    let outcomes = runner::run_parallel(jobs, &parts, 4, None);
+
+    // Each input is followed by its Windows variant, all parts each.
+    let mut failures = vec![];
+    for pair in outcomes.chunks(2 * parts.len()) {
+        let (plain, windows) = pair.split_at(parts.len());
+        for (plain, windows) in plain.iter().zip(windows) {
+            if plain.result != windows.result {
+                failures.push(format!(
+                    "{} day {} part {}: got {:?}, with CRLF {:?}",
+                    plain.year, plain.day, plain.part, plain.result, windows.result
+                ));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "answers differ for input saved on Windows:\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,10 @@
+//! Helpers shared by the integration tests.
+
+/// The text as if saved on Windows: CRLF line endings, trailing spaces
+/// on each line, and extra blank lines at the end.
+pub fn windows_variant(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("{} \r\n", line))
+        .collect::<String>()
+        + "\r\n\r\n"
+}
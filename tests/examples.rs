@@ -0,0 +1,71 @@
+//! Check the solvers against the examples from the puzzle
+//! descriptions.
+//!
+//! Every `aocNex*.dat` file in the source tree, of every year, that
+//! has an `.expected` sidecar is solved and the answers compared with
+//! the expected ones, so adding an example test is just a matter of
+//! adding the two files.
+//!
+//! Each example is also solved as if saved on Windows, with CRLF line
+//! endings, trailing spaces, and extra blank lines at the end, which
+//! has to give the same answers.
+
+use adventofcode::{
+    expected::{self, Expected},
+    input, registry, runner,
+};
+use common::windows_variant;
+use std::fs::read_to_string;
+
+mod common;
+
+#[test]
+fn examples() {
+    let mut failures = vec![];
+    let mut checked = 0;
+    let examples = registry::years().into_iter().flat_map(|year| {
+        let dir = input::year_dir(&input::default_dir(), year);
+        let examples = input::find_examples(&dir).unwrap_or_default();
+        examples.into_iter().map(move |example| (year, example))
+    });
+    for (year, example) in examples {
+        let sidecar = expected::sidecar(&example.path);
+        let day = match registry::find(year, example.day) {
+            Some(day) if sidecar.exists() => day,
+            _ => continue,
+        };
+        let expected = Expected::read(&sidecar).unwrap();
+        let text = read_to_string(&example.path).unwrap();
+        let file_name = example.path.file_name().unwrap().to_string_lossy();
+        let variants = [
+            (file_name.to_string(), text.clone()),
+            (format!("{} (CRLF)", file_name), windows_variant(&text)),
+        ];
+        for (name, text) in &variants {
+            for outcome in runner::run(day, text, &expected.parts()) {
+                let want = expected.get(outcome.part).unwrap();
+                match outcome.result {
+                    Ok(answer) if answer.to_string() == want => (),
+                    Ok(answer) => failures.push(format!(
+                        "{} part {}: expected {}, got {}",
+                        name, outcome.part, want, answer
+                    )),
+                    Err(err) => failures.push(format!(
+                        "{} part {}: expected {}, got error: {}",
+                        name, outcome.part, want, err
+                    )),
+                }
+                checked += 1;
+            }
+        }
+    }
+
+    assert!(checked > 0, "no examples with expected answers found");
+    assert!(
+        failures.is_empty(),
+        "{} of {} example answers are wrong:\n{}",
+        failures.len(),
+        checked,
+        failures.join("\n")
+    );
+}